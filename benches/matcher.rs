@@ -0,0 +1,86 @@
+//! Fuzzy matching and ranking over a large synthetic cache
+//!
+//! Run with `cargo bench --bench matcher`
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+// gg is a binary crate, so pull in the modules the matcher needs directly
+// (their unit tests aren't built here, which leaves test-only imports unused)
+#[allow(dead_code, unused_imports)]
+#[path = "../src/domain/mod.rs"]
+mod domain;
+#[allow(dead_code, unused_imports)]
+#[path = "../src/tui/matcher.rs"]
+mod matcher;
+
+use domain::Repo;
+use matcher::RepoMatcher;
+
+const REPO_COUNT: usize = 10_000;
+
+const WORDS: [&str; 16] = [
+    "api", "web", "core", "auth", "billing", "search", "infra", "docs", "mobile", "data",
+    "gateway", "worker", "client", "sdk", "deploy", "metrics",
+];
+
+/// Repos spread over 50 owners with names like `billing-worker-42`, a third of them used
+fn synthetic_repos() -> Vec<Repo> {
+    (0..REPO_COUNT)
+        .map(|i| {
+            let owner = format!("org{}", i % 50);
+            let name = format!(
+                "{}-{}-{}",
+                WORDS[i % WORDS.len()],
+                WORDS[(i / WORDS.len()) % WORDS.len()],
+                i
+            );
+            let mut repo = Repo::new(
+                i as i64,
+                name.clone(),
+                format!("{}/{}", owner, name),
+                (i % 50) as i64,
+                owner,
+                i % 7 == 0,
+                None,
+                Some("Rust".to_string()),
+                Some("main".to_string()),
+            );
+            for _ in 0..(i % 3) {
+                repo.record_access();
+            }
+            repo
+        })
+        .collect()
+}
+
+fn matcher_benchmark(c: &mut Criterion) {
+    let repos = synthetic_repos();
+
+    c.bench_function("inject 10k repos", |b| {
+        b.iter(|| {
+            let mut matcher = RepoMatcher::new(black_box(repos.clone()), Vec::new());
+            matcher.finish();
+            matcher.match_count()
+        })
+    });
+
+    let mut matcher = RepoMatcher::new(repos, Vec::new());
+    matcher.finish();
+
+    for query in ["", "api", "org4/bilwork", "gateway sdk"] {
+        c.bench_function(&format!("match and rank 10k repos {:?}", query), |b| {
+            b.iter(|| {
+                // Reset first so every iteration rematches from scratch
+                matcher.update_pattern(String::new());
+                matcher.finish();
+                matcher.update_pattern(black_box(query.to_string()));
+                matcher.finish();
+                matcher.matches_sorted().len()
+            })
+        });
+    }
+}
+
+criterion_group!(benches, matcher_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,406 @@
+use super::run_selection::{select_run, RunQuery};
+use crate::git::{get_github_repo, GitHubRepo};
+use crate::infrastructure::{GitHubClient, WorkflowJob, WorkflowRun, WorkflowStep};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::io::{Cursor, Read};
+
+/// Options for `gg actions logs`
+#[derive(Debug, Clone, Default)]
+pub struct LogOptions {
+    /// Workflow run to read (defaults to the run `gg watch action` would pick)
+    pub run: RunQuery,
+    /// Print whole job logs instead of only the failing steps
+    pub full: bool,
+    /// Only include jobs whose name contains this text (case-insensitive)
+    pub job: Option<String>,
+}
+
+/// Log output for one failing step, or for a whole job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogSection {
+    pub job: String,
+    /// None when the section is the whole job log
+    pub step: Option<String>,
+    pub lines: Vec<String>,
+}
+
+impl std::fmt::Display for LogSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.step {
+            Some(step) => writeln!(f, "==> {} › {}", self.job, step)?,
+            None => writeln!(f, "==> {}", self.job)?,
+        }
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Logs extracted from a workflow run
+#[derive(Debug)]
+pub struct RunLogs {
+    pub run: WorkflowRun,
+    pub sections: Vec<LogSection>,
+}
+
+/// Download the logs of a workflow run and extract the output of its failing steps
+pub async fn action_logs(token: String, options: &LogOptions) -> Result<RunLogs> {
+    let repo = get_github_repo()?;
+    let client = GitHubClient::new(token)?;
+    let run = select_run(&client, &repo, &options.run).await?;
+    fetch_run_logs(&client, &repo, run, options).await
+}
+
+async fn fetch_run_logs(
+    client: &GitHubClient,
+    repo: &GitHubRepo,
+    run: WorkflowRun,
+    options: &LogOptions,
+) -> Result<RunLogs> {
+    let jobs = client.fetch_run_jobs(&repo.owner, &repo.name, run.id).await?;
+    let jobs = select_jobs(&jobs, options.job.as_deref());
+
+    if jobs.is_empty() {
+        return Ok(RunLogs {
+            run,
+            sections: Vec::new(),
+        });
+    }
+
+    let archive = LogArchive::from_zip(
+        &client
+            .download_run_logs(&repo.owner, &repo.name, run.id)
+            .await?,
+    )?;
+
+    let sections = jobs
+        .into_iter()
+        .flat_map(|job| archive.sections_for(job, options.full))
+        .collect();
+
+    Ok(RunLogs { run, sections })
+}
+
+/// Jobs to print: those matching the `--job` filter, or every failed job without one
+fn select_jobs<'a>(jobs: &'a [WorkflowJob], filter: Option<&str>) -> Vec<&'a WorkflowJob> {
+    match filter {
+        Some(filter) => {
+            let filter = filter.to_lowercase();
+            jobs.iter()
+                .filter(|job| job.name.to_lowercase().contains(&filter))
+                .collect()
+        }
+        None => jobs
+            .iter()
+            .filter(|job| is_failure(job.conclusion.as_deref()))
+            .collect(),
+    }
+}
+
+fn is_failure(conclusion: Option<&str>) -> bool {
+    matches!(conclusion, Some("failure") | Some("timed_out"))
+}
+
+/// Text files from a run's log archive
+///
+/// The archive holds one `<n>_<job name>.txt` file with each job's full log, and
+/// usually a `<job name>/<step number>_<step name>.txt` file per step
+struct LogArchive {
+    files: Vec<(String, String)>,
+}
+
+impl LogArchive {
+    fn from_zip(bytes: &[u8]) -> Result<Self> {
+        let mut zip = zip::ZipArchive::new(Cursor::new(bytes))
+            .context("Failed to read workflow run logs (they may have expired)")?;
+
+        let mut files = Vec::with_capacity(zip.len());
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i)?;
+            if !entry.is_file() {
+                continue;
+            }
+            let name = entry.name().to_string();
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content)?;
+            files.push((name, String::from_utf8_lossy(&content).into_owned()));
+        }
+
+        Ok(Self { files })
+    }
+
+    /// Full log for a job (the top level `<n>_<job name>.txt` file)
+    fn job_log(&self, job: &WorkflowJob) -> Option<&str> {
+        self.files
+            .iter()
+            .find(|(name, _)| {
+                !name.contains('/')
+                    && name
+                        .strip_suffix(".txt")
+                        .and_then(|n| n.split_once('_'))
+                        .is_some_and(|(_, job_name)| same_name(job_name, &job.name))
+            })
+            .map(|(_, content)| content.as_str())
+    }
+
+    /// Log for a single step (the `<job name>/<number>_<step name>.txt` file)
+    fn step_log(&self, job: &WorkflowJob, step: &WorkflowStep) -> Option<&str> {
+        let prefix = format!("{}_", step.number);
+        self.files
+            .iter()
+            .find(|(name, _)| {
+                name.split_once('/').is_some_and(|(dir, file)| {
+                    same_name(dir, &job.name) && file.starts_with(&prefix)
+                })
+            })
+            .map(|(_, content)| content.as_str())
+    }
+
+    /// Sections to print for a job
+    /// Failing steps only, unless `full` is set or no individual step failed
+    fn sections_for(&self, job: &WorkflowJob, full: bool) -> Vec<LogSection> {
+        let failed_steps: Vec<&WorkflowStep> = job
+            .steps
+            .iter()
+            .filter(|step| is_failure(step.conclusion.as_deref()))
+            .collect();
+
+        if full || failed_steps.is_empty() {
+            let lines = self
+                .job_log(job)
+                .map(clean_lines)
+                .unwrap_or_else(|| vec!["(no log output found for this job)".to_string()]);
+            return vec![LogSection {
+                job: job.name.clone(),
+                step: None,
+                lines,
+            }];
+        }
+
+        failed_steps
+            .into_iter()
+            .map(|step| {
+                let lines = self
+                    .step_log(job, step)
+                    .map(clean_lines)
+                    .or_else(|| self.job_log(job).map(|log| slice_by_time(log, step)))
+                    .filter(|lines| !lines.is_empty())
+                    .unwrap_or_else(|| vec!["(no log output found for this step)".to_string()]);
+                LogSection {
+                    job: job.name.clone(),
+                    step: Some(step.name.clone()),
+                    lines,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Compare names ignoring the characters GitHub replaces in archive file names
+fn same_name(archive_name: &str, name: &str) -> bool {
+    let normalize = |s: &str| -> String {
+        s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    normalize(archive_name) == normalize(name)
+}
+
+/// Split a log line into its leading timestamp (if any) and message
+fn split_timestamp(line: &str) -> (Option<DateTime<Utc>>, &str) {
+    let line = line.trim_start_matches('\u{feff}');
+    match line.split_once(' ') {
+        Some((stamp, rest)) => match stamp.parse::<DateTime<Utc>>() {
+            Ok(at) => (Some(at), rest),
+            Err(_) => (None, line),
+        },
+        None => (None, line),
+    }
+}
+
+/// Log lines with their timestamps removed
+fn clean_lines(log: &str) -> Vec<String> {
+    log.lines()
+        .map(|line| split_timestamp(line).1.to_string())
+        .collect()
+}
+
+/// Lines of a full job log written while `step` was running
+/// Step times only have second precision, so the end is widened by a second
+fn slice_by_time(log: &str, step: &WorkflowStep) -> Vec<String> {
+    let (Some(start), Some(end)) = (step.started_at, step.completed_at) else {
+        return Vec::new();
+    };
+    let end = end + chrono::Duration::seconds(1);
+
+    let mut inside = false;
+    log.lines()
+        .filter_map(|line| {
+            let (at, message) = split_timestamp(line);
+            // Lines without a timestamp continue the previous line
+            if let Some(at) = at {
+                inside = at >= start && at < end;
+            }
+            inside.then(|| message.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn step(number: u32, name: &str, conclusion: &str, start: &str, end: &str) -> WorkflowStep {
+        WorkflowStep {
+            number,
+            name: name.to_string(),
+            status: "completed".to_string(),
+            conclusion: Some(conclusion.to_string()),
+            started_at: Some(start.parse().unwrap()),
+            completed_at: Some(end.parse().unwrap()),
+        }
+    }
+
+    fn failed_job() -> WorkflowJob {
+        WorkflowJob {
+            id: 7,
+            run_id: 42,
+            run_attempt: 1,
+            name: "test / linux".to_string(),
+            status: "completed".to_string(),
+            conclusion: Some("failure".to_string()),
+            created_at: None,
+            started_at: None,
+            completed_at: None,
+            steps: vec![
+                step(1, "Set up job", "success", "2026-01-01T10:00:00Z", "2026-01-01T10:00:02Z"),
+                step(2, "Run tests", "failure", "2026-01-01T10:00:02Z", "2026-01-01T10:00:05Z"),
+            ],
+        }
+    }
+
+    const JOB_LOG: &str = "\u{feff}2026-01-01T10:00:00.1000000Z Setting up runner\n\
+        2026-01-01T10:00:02.5000000Z running 3 tests\n\
+        2026-01-01T10:00:04.9000000Z test it_works ... FAILED\n\
+        assertion failed: left == right\n\
+        2026-01-01T10:00:06.2000000Z Cleaning up\n";
+
+    /// Log archive laid out the way GitHub serves it
+    fn fixture_log_zip(with_step_files: bool) -> Vec<u8> {
+        let mut buf = Cursor::new(Vec::new());
+        let mut zip = zip::ZipWriter::new(&mut buf);
+        let options = zip::write::SimpleFileOptions::default();
+
+        zip.start_file("0_test  linux.txt", options).unwrap();
+        zip.write_all(JOB_LOG.as_bytes()).unwrap();
+        if with_step_files {
+            zip.start_file("test  linux/1_Set up job.txt", options).unwrap();
+            zip.write_all(b"2026-01-01T10:00:00.1000000Z Setting up runner\n").unwrap();
+            zip.start_file("test  linux/2_Run tests.txt", options).unwrap();
+            zip.write_all(b"2026-01-01T10:00:04.9000000Z test it_works ... FAILED\n").unwrap();
+        }
+        zip.finish().unwrap();
+        buf.into_inner()
+    }
+
+    #[test]
+    fn test_failing_step_from_step_file() {
+        let archive = LogArchive::from_zip(&fixture_log_zip(true)).unwrap();
+        let sections = archive.sections_for(&failed_job(), false);
+
+        assert_eq!(
+            sections,
+            vec![LogSection {
+                job: "test / linux".to_string(),
+                step: Some("Run tests".to_string()),
+                lines: vec!["test it_works ... FAILED".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_failing_step_sliced_from_job_log() {
+        let archive = LogArchive::from_zip(&fixture_log_zip(false)).unwrap();
+        let sections = archive.sections_for(&failed_job(), false);
+
+        assert_eq!(
+            sections[0].lines,
+            vec![
+                "running 3 tests",
+                "test it_works ... FAILED",
+                "assertion failed: left == right"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_full_job_log() {
+        let archive = LogArchive::from_zip(&fixture_log_zip(true)).unwrap();
+        let sections = archive.sections_for(&failed_job(), true);
+
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].step, None);
+        assert_eq!(sections[0].lines.len(), 5);
+        assert_eq!(sections[0].lines[0], "Setting up runner");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_run_logs_from_api() {
+        let mut server = mockito::Server::new_async().await;
+        let jobs = serde_json::json!({
+            "total_count": 2,
+            "jobs": [
+                {"name": "lint", "status": "completed", "conclusion": "success",
+                 "started_at": null, "completed_at": null, "steps": []},
+                {"name": "test / linux", "status": "completed", "conclusion": "failure",
+                 "started_at": null, "completed_at": null, "steps": [
+                    {"number": 2, "name": "Run tests", "status": "completed", "conclusion": "failure",
+                     "started_at": "2026-01-01T10:00:02Z", "completed_at": "2026-01-01T10:00:05Z"}
+                 ]}
+            ]
+        });
+        server
+            .mock("GET", "/repos/octocat/hello/actions/runs/42/jobs?page=1&per_page=100")
+            .with_header("content-type", "application/json")
+            .with_body(jobs.to_string())
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/repos/octocat/hello/actions/runs/42/logs")
+            .with_status(302)
+            .with_header("location", &format!("{}/blob/logs.zip", server.url()))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/blob/logs.zip")
+            .with_header("content-type", "application/zip")
+            .with_body(fixture_log_zip(true))
+            .create_async()
+            .await;
+
+        let client = GitHubClient::with_base_url("token".to_string(), &server.url()).unwrap();
+        let repo = GitHubRepo {
+            owner: "octocat".to_string(),
+            name: "hello".to_string(),
+        };
+        let run: WorkflowRun = serde_json::from_value(serde_json::json!({
+            "id": 42, "name": "CI", "status": "completed", "conclusion": "failure",
+            "head_branch": "main", "head_sha": "abc123", "event": "push",
+            "html_url": "https://github.com/octocat/hello/actions/runs/42",
+            "created_at": "2026-01-01T10:00:00Z", "updated_at": "2026-01-01T10:00:10Z"
+        }))
+        .unwrap();
+
+        let logs = fetch_run_logs(&client, &repo, run, &LogOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(logs.sections.len(), 1);
+        assert_eq!(logs.sections[0].step.as_deref(), Some("Run tests"));
+        assert_eq!(logs.sections[0].lines, vec!["test it_works ... FAILED"]);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::domain::Repo;
+use crate::infrastructure::{GitHubClient, RunFilter, WorkflowRun};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinSet;
+
+/// Update from the dashboard poller
+#[derive(Debug)]
+pub enum DashboardEvent {
+    /// Latest run for a repo (None if it has never run a workflow)
+    Run {
+        full_name: String,
+        run: Option<Box<WorkflowRun>>,
+    },
+    /// Fetching a repo's runs failed
+    Failed { full_name: String, message: String },
+    /// Every repo has been polled once more
+    Polled,
+}
+
+/// The `count` most frecent repos, most used first
+/// Falls back to the remaining repos in cache order when fewer have been used
+pub fn dashboard_repos(mut repos: Vec<Repo>, count: usize) -> Vec<Repo> {
+    repos.sort_by(|a, b| b.score().total_cmp(&a.score()));
+    repos.truncate(count);
+    repos
+}
+
+/// Poll the latest workflow run of each repo every `interval` on the tokio runtime
+/// Stops once the returned receiver is dropped
+pub fn spawn_dashboard_poll(token: String, repos: Vec<String>, interval: Duration) -> Receiver<DashboardEvent> {
+    let (tx, rx) = mpsc::channel();
+
+    tokio::spawn(async move {
+        let client = match GitHubClient::new(token) {
+            Ok(client) => Arc::new(client),
+            Err(e) => {
+                for full_name in repos {
+                    let _ = tx.send(DashboardEvent::Failed {
+                        full_name,
+                        message: format!("{:#}", e),
+                    });
+                }
+                return;
+            }
+        };
+
+        loop {
+            let mut fetches = JoinSet::new();
+            for full_name in repos.iter().cloned() {
+                let client = Arc::clone(&client);
+                fetches.spawn(async move {
+                    let result = match full_name.split_once('/') {
+                        Some((owner, name)) => client
+                            .fetch_workflow_runs(owner, name, &RunFilter::default(), 1)
+                            .await
+                            .map(|runs| runs.into_iter().next()),
+                        None => Err(anyhow::anyhow!("Invalid repo name {}", full_name)),
+                    };
+                    match result {
+                        Ok(run) => DashboardEvent::Run {
+                            full_name,
+                            run: run.map(Box::new),
+                        },
+                        Err(e) => DashboardEvent::Failed {
+                            full_name,
+                            message: format!("{:#}", e),
+                        },
+                    }
+                });
+            }
+
+            while let Some(event) = fetches.join_next().await {
+                if let Ok(event) = event
+                    && tx.send(event).is_err()
+                {
+                    return;
+                }
+            }
+            if tx.send(DashboardEvent::Polled).is_err() {
+                return;
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    });
+
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(id: i64, full_name: &str, accesses: u32) -> Repo {
+        let (owner, name) = full_name.split_once('/').unwrap();
+        let mut repo = Repo::new(
+            id,
+            name.to_string(),
+            full_name.to_string(),
+            1,
+            owner.to_string(),
+            false,
+            None,
+            None,
+            None,
+        );
+        for _ in 0..accesses {
+            repo.record_access();
+        }
+        repo
+    }
+
+    #[test]
+    fn test_dashboard_repos_by_frecency() {
+        let repos = vec![
+            repo(1, "org/never", 0),
+            repo(2, "org/sometimes", 3),
+            repo(3, "org/often", 20),
+        ];
+
+        let picked: Vec<String> = dashboard_repos(repos, 2)
+            .into_iter()
+            .map(|r| r.full_name)
+            .collect();
+
+        assert_eq!(picked, vec!["org/often", "org/sometimes"]);
+    }
+}
@@ -0,0 +1,525 @@
+use super::watch_action::format_duration;
+use crate::git::{get_github_repo, GitHubRepo};
+use crate::infrastructure::{Cache, GitHubClient, RunHistory, RunHistorySync, WorkflowJob, WorkflowRun};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+/// Cached run history synced more recently than this is used without asking the API
+const SYNC_TTL_MINUTES: i64 = 15;
+
+/// Number of runs whose jobs are fetched at the same time
+const JOB_FETCH_CONCURRENCY: usize = 8;
+
+/// A job attempt and the run it belongs to
+type JobAttempt<'a> = (&'a WorkflowRun, &'a WorkflowJob);
+
+/// Options for `gg actions stats`
+#[derive(Debug, Clone)]
+pub struct StatsOptions {
+    /// Only runs of this workflow file (e.g. ci.yml) or workflow name
+    pub workflow: Option<String>,
+    /// How many days of history to include
+    pub days: u32,
+    /// Sync with the API even if the cached history is recent
+    pub refresh: bool,
+}
+
+/// Workflow run analytics for a repo
+#[derive(Debug, Clone, Serialize)]
+pub struct RunStats {
+    pub repo: String,
+    pub days: u32,
+    pub workflows: Vec<WorkflowStats>,
+    pub flaky_jobs: Vec<FlakyJob>,
+}
+
+/// Analytics for one workflow
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkflowStats {
+    pub name: String,
+    pub runs: usize,
+    /// Share of runs that succeeded, not counting cancelled or skipped runs
+    pub success_rate: Option<f64>,
+    pub duration_p50_secs: Option<i64>,
+    pub duration_p95_secs: Option<i64>,
+    /// Time jobs waited for a runner
+    pub queue_p50_secs: Option<i64>,
+    pub queue_p95_secs: Option<i64>,
+}
+
+/// A job that failed and then passed on a rerun of the same commit
+#[derive(Debug, Clone, Serialize)]
+pub struct FlakyJob {
+    pub workflow: String,
+    pub job: String,
+    /// Number of commits where the job flipped from failing to passing
+    pub commits: usize,
+    /// Most recent run where it happened
+    pub url: String,
+}
+
+/// Success rate, durations, queue times and flaky jobs of the current repo's workflow runs
+/// Run history is cached, so only runs that are new or were re-run since the last sync cost API calls
+pub async fn actions_stats(token: String, cache: &Cache, options: &StatsOptions, quiet: bool) -> Result<RunStats> {
+    let repo = get_github_repo()?;
+    let full_name = format!("{}/{}", repo.owner, repo.name);
+    let now = Utc::now();
+    let since = window_start(now, options.days)?;
+
+    if options.refresh || needs_sync(cache.run_history_sync(&full_name)?, since, now) {
+        let client = GitHubClient::new(token)?;
+        sync_run_history(client, cache, &repo, since, quiet).await?;
+    }
+
+    let history = cache.load_run_history(&full_name, since)?;
+    Ok(compute_stats(
+        full_name,
+        options.days,
+        &history,
+        options.workflow.as_deref(),
+    ))
+}
+
+/// Start of the `days` long window ending at `now`
+fn window_start(now: DateTime<Utc>, days: u32) -> Result<DateTime<Utc>> {
+    chrono::Duration::try_days(days.into())
+        .and_then(|days| now.checked_sub_signed(days))
+        .ok_or_else(|| anyhow::anyhow!("--days is too large"))
+}
+
+/// Whether the cached history is missing, too short or too old to answer for `since`
+fn needs_sync(sync: Option<RunHistorySync>, since: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    match sync {
+        Some(sync) => {
+            sync.since > since || now - sync.synced_at > chrono::Duration::minutes(SYNC_TTL_MINUTES)
+        }
+        None => true,
+    }
+}
+
+/// Fetch completed runs since `since`, plus the jobs of runs that are new or were re-run
+async fn sync_run_history(
+    client: GitHubClient,
+    cache: &Cache,
+    repo: &GitHubRepo,
+    since: DateTime<Utc>,
+    quiet: bool,
+) -> Result<()> {
+    let full_name = format!("{}/{}", repo.owner, repo.name);
+    let runs = client
+        .fetch_run_history(&repo.owner, &repo.name, since.date_naive())
+        .await?;
+    let cached = cache.cached_run_attempts(&full_name)?;
+    let stale: Vec<u64> = runs
+        .iter()
+        .filter(|run| cached.get(&run.id) != Some(&run.run_attempt))
+        .map(|run| run.id)
+        .collect();
+
+    let progress = if !quiet && !stale.is_empty() {
+        let pb = ProgressBar::new(stale.len() as u64);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("{spinner:.magenta.bold} Fetching jobs {pos}/{len}")
+                .unwrap(),
+        );
+        Some(pb)
+    } else {
+        None
+    };
+
+    let client = Arc::new(client);
+    let mut stale = stale.into_iter();
+    let mut fetches = JoinSet::new();
+    let mut jobs = Vec::new();
+    let mut failures = Vec::new();
+    loop {
+        while fetches.len() < JOB_FETCH_CONCURRENCY
+            && let Some(run_id) = stale.next()
+        {
+            let client = Arc::clone(&client);
+            let (owner, name) = (repo.owner.clone(), repo.name.clone());
+            fetches.spawn(async move {
+                (run_id, client.fetch_run_job_history(&owner, &name, run_id).await)
+            });
+        }
+
+        let Some(fetched) = fetches.join_next().await else {
+            break;
+        };
+        match fetched? {
+            (run_id, Ok(run_jobs)) => jobs.push((run_id, run_jobs)),
+            (_, Err(e)) => failures.push(e),
+        }
+        if let Some(ref pb) = progress {
+            pb.inc(1);
+        }
+    }
+    if let Some(pb) = progress {
+        pb.finish_and_clear();
+    }
+
+    // Keep what was fetched, so a retry after a failure only fetches the rest
+    let fetched: HashSet<u64> = jobs.iter().map(|(run_id, _)| *run_id).collect();
+    let complete: Vec<WorkflowRun> = runs
+        .into_iter()
+        .filter(|run| cached.get(&run.id) == Some(&run.run_attempt) || fetched.contains(&run.id))
+        .collect();
+    cache.store_run_history(&full_name, &complete, &jobs)?;
+
+    if let Some(e) = failures.into_iter().next() {
+        return Err(e);
+    }
+    cache.set_run_history_synced(&full_name, since)
+}
+
+/// Whether `run` belongs to the workflow given by file (e.g. ci.yml) or name
+fn matches_workflow(run: &WorkflowRun, workflow: &str) -> bool {
+    run.name.eq_ignore_ascii_case(workflow)
+        || run
+            .path
+            .as_deref()
+            .and_then(|path| path.rsplit('/').next())
+            .is_some_and(|file| file == workflow)
+}
+
+fn compute_stats(repo: String, days: u32, history: &RunHistory, workflow: Option<&str>) -> RunStats {
+    let runs: Vec<&WorkflowRun> = history
+        .runs
+        .iter()
+        .filter(|run| workflow.is_none_or(|w| matches_workflow(run, w)))
+        .collect();
+    let runs_by_id: HashMap<u64, &WorkflowRun> = runs.iter().map(|run| (run.id, *run)).collect();
+    let jobs: Vec<JobAttempt> = history
+        .jobs
+        .iter()
+        .filter_map(|job| Some((*runs_by_id.get(&job.run_id)?, job)))
+        .collect();
+
+    let mut by_workflow: BTreeMap<&str, Vec<&WorkflowRun>> = BTreeMap::new();
+    for run in &runs {
+        by_workflow.entry(&run.name).or_default().push(run);
+    }
+
+    let mut workflows: Vec<WorkflowStats> = by_workflow
+        .into_iter()
+        .map(|(name, runs)| {
+            let decided: Vec<&str> = runs
+                .iter()
+                .filter_map(|run| run.conclusion.as_deref())
+                .filter(|c| !matches!(*c, "cancelled" | "skipped" | "neutral" | "action_required" | "stale"))
+                .collect();
+            let success_rate = (!decided.is_empty()).then(|| {
+                decided.iter().filter(|c| **c == "success").count() as f64 / decided.len() as f64
+            });
+
+            let mut durations: Vec<i64> = runs.iter().map(|run| run.elapsed().num_seconds()).collect();
+            durations.sort_unstable();
+
+            let mut queued: Vec<i64> = jobs
+                .iter()
+                .filter(|(run, _)| run.name == name)
+                .filter_map(|(_, job)| Some((job.started_at? - job.created_at?).num_seconds().max(0)))
+                .collect();
+            queued.sort_unstable();
+
+            WorkflowStats {
+                name: name.to_string(),
+                runs: runs.len(),
+                success_rate,
+                duration_p50_secs: percentile(&durations, 50),
+                duration_p95_secs: percentile(&durations, 95),
+                queue_p50_secs: percentile(&queued, 50),
+                queue_p95_secs: percentile(&queued, 95),
+            }
+        })
+        .collect();
+    workflows.sort_by_key(|w| std::cmp::Reverse(w.runs));
+
+    RunStats {
+        repo,
+        days,
+        workflows,
+        flaky_jobs: flaky_jobs(&jobs),
+    }
+}
+
+/// Jobs that failed and later passed for the same workflow and commit, most flaky first
+fn flaky_jobs(jobs: &[JobAttempt]) -> Vec<FlakyJob> {
+    // Every attempt of a job on a commit, across reruns and separate runs
+    let mut attempts: HashMap<(&str, &str, &str), Vec<JobAttempt>> = HashMap::new();
+    for (run, job) in jobs {
+        attempts
+            .entry((run.name.as_str(), run.head_sha.as_str(), job.name.as_str()))
+            .or_default()
+            .push((run, job));
+    }
+
+    // Number of commits each job flipped on, and the latest run where it passed
+    let mut flips: HashMap<(&str, &str), (usize, &WorkflowRun)> = HashMap::new();
+    for ((workflow, _, job_name), mut tries) in attempts {
+        tries.sort_by_key(|(run, job)| (run.created_at, job.run_attempt, job.started_at));
+        let first_failure = tries.iter().position(|(_, job)| {
+            matches!(job.conclusion.as_deref(), Some("failure") | Some("timed_out"))
+        });
+        let Some((passed, _)) = first_failure.and_then(|failed| {
+            tries[failed..]
+                .iter()
+                .rev()
+                .find(|(_, job)| job.conclusion.as_deref() == Some("success"))
+        }) else {
+            continue;
+        };
+
+        flips
+            .entry((workflow, job_name))
+            .and_modify(|(commits, latest)| {
+                *commits += 1;
+                if passed.created_at > latest.created_at {
+                    *latest = passed;
+                }
+            })
+            .or_insert((1, passed));
+    }
+
+    let mut flaky: Vec<FlakyJob> = flips
+        .into_iter()
+        .map(|((workflow, job), (commits, latest))| FlakyJob {
+            workflow: workflow.to_string(),
+            job: job.to_string(),
+            commits,
+            url: latest.html_url.clone(),
+        })
+        .collect();
+    flaky.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then_with(|| (&a.workflow, &a.job).cmp(&(&b.workflow, &b.job)))
+    });
+    flaky
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[i64], p: usize) -> Option<i64> {
+    let rank = (p * sorted.len()).div_ceil(100);
+    sorted.get(rank.saturating_sub(1)).copied()
+}
+
+impl std::fmt::Display for RunStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let runs: usize = self.workflows.iter().map(|w| w.runs).sum();
+        writeln!(f, "{} · last {} days · {} runs", self.repo, self.days, runs)?;
+        if self.workflows.is_empty() {
+            return Ok(());
+        }
+
+        let seconds = |secs: Option<i64>| {
+            secs.map(|s| format_duration(chrono::Duration::seconds(s)))
+                .unwrap_or_else(|| "-".to_string())
+        };
+        let name_width = self
+            .workflows
+            .iter()
+            .map(|w| w.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Workflow".len());
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<name_width$}  {:>5}  {:>7}  {:>8}  {:>8}  {:>9}  {:>9}",
+            "Workflow", "Runs", "Success", "p50", "p95", "Queue p50", "Queue p95"
+        )?;
+        for workflow in &self.workflows {
+            writeln!(
+                f,
+                "{:<name_width$}  {:>5}  {:>7}  {:>8}  {:>8}  {:>9}  {:>9}",
+                workflow.name,
+                workflow.runs,
+                workflow
+                    .success_rate
+                    .map(|rate| format!("{:.1}%", rate * 100.0))
+                    .unwrap_or_else(|| "-".to_string()),
+                seconds(workflow.duration_p50_secs),
+                seconds(workflow.duration_p95_secs),
+                seconds(workflow.queue_p50_secs),
+                seconds(workflow.queue_p95_secs),
+            )?;
+        }
+
+        if !self.flaky_jobs.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Flaky jobs (failed, then passed on the same commit):")?;
+            for job in &self.flaky_jobs {
+                writeln!(
+                    f,
+                    "  {} › {}  {} commit(s)  {}",
+                    job.workflow, job.job, job.commits, job.url
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_json(id: u64, name: &str, sha: &str, conclusion: &str, attempt: u32) -> serde_json::Value {
+        serde_json::json!({
+            "id": id, "name": name, "status": "completed", "conclusion": conclusion,
+            "head_branch": "main", "head_sha": sha, "event": "push",
+            "path": format!(".github/workflows/{}.yml", name.to_lowercase()),
+            "html_url": format!("https://github.com/octocat/hello/actions/runs/{}", id),
+            "run_attempt": attempt,
+            "created_at": "2026-01-01T10:00:00Z",
+            "run_started_at": "2026-01-01T10:00:00Z",
+            "updated_at": format!("2026-01-01T10:{:02}:00Z", id),
+        })
+    }
+
+    fn run(id: u64, name: &str, sha: &str, conclusion: &str, attempt: u32) -> WorkflowRun {
+        serde_json::from_value(run_json(id, name, sha, conclusion, attempt)).unwrap()
+    }
+
+    fn job_json(id: u64, run_id: u64, attempt: u32, name: &str, conclusion: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id, "run_id": run_id, "run_attempt": attempt, "name": name,
+            "status": "completed", "conclusion": conclusion,
+            "created_at": "2026-01-01T10:00:00Z",
+            "started_at": format!("2026-01-01T10:00:{:02}Z", id),
+            "completed_at": "2026-01-01T10:01:00Z"
+        })
+    }
+
+    fn job(id: u64, run_id: u64, attempt: u32, name: &str, conclusion: &str) -> WorkflowJob {
+        serde_json::from_value(job_json(id, run_id, attempt, name, conclusion)).unwrap()
+    }
+
+    #[test]
+    fn test_percentile() {
+        let values: Vec<i64> = (1..=20).collect();
+        assert_eq!(percentile(&values, 50), Some(10));
+        assert_eq!(percentile(&values, 95), Some(19));
+        assert_eq!(percentile(&[7], 95), Some(7));
+        assert_eq!(percentile(&[], 50), None);
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let history = RunHistory {
+            runs: vec![
+                // Run 1 failed its first attempt and passed on rerun
+                run(1, "CI", "aaa", "success", 2),
+                run(2, "CI", "bbb", "failure", 1),
+                run(3, "CI", "ccc", "cancelled", 1),
+                run(4, "Deploy", "aaa", "success", 1),
+            ],
+            jobs: vec![
+                job(10, 1, 1, "test", "failure"),
+                job(20, 1, 2, "test", "success"),
+                job(30, 2, 1, "test", "failure"),
+                job(40, 4, 1, "deploy", "success"),
+            ],
+        };
+
+        let stats = compute_stats("octocat/hello".to_string(), 30, &history, None);
+
+        let ci = &stats.workflows[0];
+        assert_eq!((ci.name.as_str(), ci.runs), ("CI", 3));
+        assert_eq!(ci.success_rate, Some(0.5));
+        assert_eq!(ci.duration_p50_secs, Some(120));
+        assert_eq!(ci.duration_p95_secs, Some(180));
+        assert_eq!(ci.queue_p50_secs, Some(20));
+        assert_eq!(stats.flaky_jobs.len(), 1);
+        assert_eq!(stats.flaky_jobs[0].job, "test");
+        assert_eq!(stats.flaky_jobs[0].commits, 1);
+
+        let deploy = compute_stats("octocat/hello".to_string(), 30, &history, Some("deploy.yml"));
+        assert_eq!(deploy.workflows.len(), 1);
+        assert_eq!(deploy.workflows[0].name, "Deploy");
+        assert!(deploy.flaky_jobs.is_empty());
+    }
+
+    #[test]
+    fn test_needs_sync() {
+        let now = Utc::now();
+        let since = now - chrono::Duration::days(30);
+        let sync = |days: i64, minutes_ago: i64| RunHistorySync {
+            since: now - chrono::Duration::days(days),
+            synced_at: now - chrono::Duration::minutes(minutes_ago),
+        };
+
+        assert!(needs_sync(None, since, now));
+        assert!(!needs_sync(Some(sync(30, 5)), since, now));
+        assert!(needs_sync(Some(sync(7, 5)), since, now));
+        assert!(needs_sync(Some(sync(30, 60)), since, now));
+    }
+
+    #[test]
+    fn test_window_start() {
+        let now = Utc::now();
+        assert_eq!(window_start(now, 30).unwrap(), now - chrono::Duration::days(30));
+        assert!(window_start(now, 1_000_000_000).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sync_only_fetches_new_attempts() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/repos/octocat/hello/actions/runs")
+            .match_query(mockito::Matcher::UrlEncoded("status".into(), "completed".into()))
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({"workflow_runs": [
+                    run_json(1, "CI", "aaa", "success", 2),
+                    run_json(2, "CI", "bbb", "failure", 1),
+                ]})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let jobs = server
+            .mock("GET", "/repos/octocat/hello/actions/runs/1/jobs?filter=all&page=1&per_page=100")
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({"jobs": [
+                    job_json(10, 1, 1, "test", "failure"),
+                    job_json(20, 1, 2, "test", "success"),
+                ]})
+                .to_string(),
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let cache = Cache::in_memory().unwrap();
+        // Run 2 is already cached at its latest attempt
+        cache
+            .store_run_history("octocat/hello", &[run(2, "CI", "bbb", "failure", 1)], &[])
+            .unwrap();
+
+        let client = GitHubClient::with_base_url("token".to_string(), &server.url()).unwrap();
+        let repo = GitHubRepo {
+            owner: "octocat".to_string(),
+            name: "hello".to_string(),
+        };
+        let since = "2025-12-01T00:00:00Z".parse().unwrap();
+        sync_run_history(client, &cache, &repo, since, true).await.unwrap();
+
+        jobs.assert_async().await;
+        let history = cache.load_run_history("octocat/hello", since).unwrap();
+        assert_eq!(history.runs.len(), 2);
+        assert_eq!(history.jobs.len(), 2);
+        assert!(cache.run_history_sync("octocat/hello").unwrap().is_some());
+        assert_eq!(cache.cached_run_attempts("octocat/hello").unwrap()[&1], 2);
+    }
+}
@@ -0,0 +1,248 @@
+use super::run_selection::{select_run, RunQuery};
+use crate::git::{get_github_repo, GitHubRepo};
+use crate::infrastructure::{Artifact, GitHubClient, WorkflowRun};
+use anyhow::{bail, Context, Result};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// Artifacts of a workflow run
+#[derive(Debug)]
+pub struct RunArtifacts {
+    pub run: WorkflowRun,
+    pub artifacts: Vec<Artifact>,
+}
+
+/// An artifact extracted to disk
+#[derive(Debug)]
+pub struct DownloadedArtifact {
+    pub name: String,
+    pub path: PathBuf,
+    pub files: usize,
+}
+
+impl std::fmt::Display for DownloadedArtifact {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Downloaded {} ({} file(s)) to {}",
+            self.name,
+            self.files,
+            self.path.display()
+        )
+    }
+}
+
+/// List the artifacts of the selected run
+pub async fn list_artifacts(token: String, query: &RunQuery) -> Result<RunArtifacts> {
+    let repo = get_github_repo()?;
+    let client = GitHubClient::new(token)?;
+    let run = select_run(&client, &repo, query).await?;
+    let artifacts = client
+        .fetch_run_artifacts(&repo.owner, &repo.name, run.id)
+        .await?;
+    Ok(RunArtifacts { run, artifacts })
+}
+
+/// Download and unzip artifacts of the selected run into `dir`
+/// A named artifact is extracted straight into `dir`; without a name every artifact
+/// gets its own `dir/<name>` directory
+pub async fn download_artifacts(
+    token: String,
+    query: &RunQuery,
+    name: Option<&str>,
+    dir: &Path,
+) -> Result<Vec<DownloadedArtifact>> {
+    let repo = get_github_repo()?;
+    let client = GitHubClient::new(token)?;
+    let run = select_run(&client, &repo, query).await?;
+    download_run_artifacts(&client, &repo, &run, name, dir).await
+}
+
+async fn download_run_artifacts(
+    client: &GitHubClient,
+    repo: &GitHubRepo,
+    run: &WorkflowRun,
+    name: Option<&str>,
+    dir: &Path,
+) -> Result<Vec<DownloadedArtifact>> {
+    let artifacts = client
+        .fetch_run_artifacts(&repo.owner, &repo.name, run.id)
+        .await?;
+    let selected = select_artifacts(&artifacts, name, run)?;
+
+    let mut downloaded = Vec::with_capacity(selected.len());
+    for artifact in selected {
+        let path = match name {
+            Some(_) => dir.to_path_buf(),
+            None => dir.join(&artifact.name),
+        };
+        let bytes = client
+            .download_artifact(&repo.owner, &repo.name, artifact.id)
+            .await?;
+        let files = extract_zip(&bytes, &path)
+            .with_context(|| format!("Failed to extract artifact {}", artifact.name))?;
+        downloaded.push(DownloadedArtifact {
+            name: artifact.name.clone(),
+            path,
+            files,
+        });
+    }
+
+    Ok(downloaded)
+}
+
+/// The artifact called `name`, or every artifact that hasn't expired
+fn select_artifacts<'a>(
+    artifacts: &'a [Artifact],
+    name: Option<&str>,
+    run: &WorkflowRun,
+) -> Result<Vec<&'a Artifact>> {
+    let Some(name) = name else {
+        let available: Vec<&Artifact> = artifacts.iter().filter(|a| !a.expired).collect();
+        if available.is_empty() {
+            bail!("{} #{} has no artifacts to download", run.name, run.id);
+        }
+        return Ok(available);
+    };
+
+    match artifacts.iter().find(|a| a.name == name) {
+        Some(artifact) if artifact.expired => {
+            bail!("Artifact {} of {} #{} has expired", name, run.name, run.id)
+        }
+        Some(artifact) => Ok(vec![artifact]),
+        None => {
+            let names: Vec<&str> = artifacts.iter().map(|a| a.name.as_str()).collect();
+            if names.is_empty() {
+                bail!("{} #{} has no artifacts", run.name, run.id);
+            }
+            bail!(
+                "No artifact named {} in {} #{}, available: {}",
+                name,
+                run.name,
+                run.id,
+                names.join(", ")
+            )
+        }
+    }
+}
+
+/// Extract a zip archive into `dir`, returning the number of files written
+/// Entries that would escape `dir` are rejected by the zip crate
+fn extract_zip(bytes: &[u8], dir: &Path) -> Result<usize> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(bytes))?;
+    let files = (0..zip.len())
+        .filter(|&i| zip.by_index_raw(i).is_ok_and(|entry| entry.is_file()))
+        .count();
+    std::fs::create_dir_all(dir)?;
+    zip.extract(dir)?;
+    Ok(files)
+}
+
+/// Format a byte count compactly, e.g. `512 B`, `3.4 KB` or `12.0 MB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn artifact_zip() -> Vec<u8> {
+        let mut buf = Cursor::new(Vec::new());
+        let mut zip = zip::ZipWriter::new(&mut buf);
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("app.apk", options).unwrap();
+        zip.write_all(b"apk").unwrap();
+        zip.start_file("reports/junit.xml", options).unwrap();
+        zip.write_all(b"<testsuite/>").unwrap();
+        zip.finish().unwrap();
+        buf.into_inner()
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(3482), "3.4 KB");
+        assert_eq!(format_size(12 * 1024 * 1024), "12.0 MB");
+    }
+
+    #[tokio::test]
+    async fn test_download_artifacts() {
+        let mut server = mockito::Server::new_async().await;
+        let artifacts = serde_json::json!({
+            "total_count": 2,
+            "artifacts": [
+                {"id": 1, "name": "android", "size_in_bytes": 2048, "expired": false,
+                 "expires_at": "2026-02-01T00:00:00Z"},
+                {"id": 2, "name": "coverage", "size_in_bytes": 100, "expired": true,
+                 "expires_at": "2026-01-02T00:00:00Z"}
+            ]
+        });
+        server
+            .mock("GET", "/repos/octocat/hello/actions/runs/42/artifacts?page=1&per_page=100")
+            .with_header("content-type", "application/json")
+            .with_body(artifacts.to_string())
+            .expect(3)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/repos/octocat/hello/actions/artifacts/1/zip")
+            .with_status(302)
+            .with_header("location", &format!("{}/blob/android.zip", server.url()))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/blob/android.zip")
+            .with_header("content-type", "application/zip")
+            .with_body(artifact_zip())
+            .create_async()
+            .await;
+
+        let client = GitHubClient::with_base_url("token".to_string(), &server.url()).unwrap();
+        let repo = GitHubRepo {
+            owner: "octocat".to_string(),
+            name: "hello".to_string(),
+        };
+        let run: WorkflowRun = serde_json::from_value(serde_json::json!({
+            "id": 42, "name": "CI", "status": "completed", "conclusion": "success",
+            "head_branch": "main", "head_sha": "abc123", "event": "push",
+            "html_url": "", "created_at": "2026-01-01T10:00:00Z", "updated_at": "2026-01-01T10:05:00Z"
+        }))
+        .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        // Without a name every unexpired artifact gets its own directory
+        let downloaded = download_run_artifacts(&client, &repo, &run, None, dir.path())
+            .await
+            .unwrap();
+        assert_eq!(downloaded.len(), 1);
+        assert_eq!(downloaded[0].files, 2);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("android/reports/junit.xml")).unwrap(),
+            "<testsuite/>"
+        );
+
+        // A named artifact is extracted straight into the directory
+        let named = dir.path().join("named");
+        download_run_artifacts(&client, &repo, &run, Some("android"), &named)
+            .await
+            .unwrap();
+        assert!(named.join("app.apk").exists());
+
+        let expired = download_run_artifacts(&client, &repo, &run, Some("coverage"), dir.path())
+            .await
+            .unwrap_err();
+        assert!(expired.to_string().contains("expired"));
+    }
+}
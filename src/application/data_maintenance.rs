@@ -0,0 +1,115 @@
+use super::data_refresh::{refresh_cache, RefreshResult};
+use crate::infrastructure::Cache;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Outcome of checking the cache database
+#[derive(Debug)]
+pub enum CheckResult {
+    /// `PRAGMA integrity_check` found no problems
+    Healthy,
+    /// The database opened but has integrity problems
+    Corrupt(Vec<String>),
+    /// The database could not be opened at all
+    Unreadable(String),
+}
+
+impl std::fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckResult::Healthy => write!(f, "Cache OK"),
+            CheckResult::Corrupt(problems) => {
+                writeln!(f, "Cache is corrupt ({} problem(s)):", problems.len())?;
+                for problem in problems {
+                    writeln!(f, "  {}", problem)?;
+                }
+                Ok(())
+            }
+            CheckResult::Unreadable(error) => write!(f, "Cache could not be opened: {}", error),
+        }
+    }
+}
+
+/// Run an integrity check on the cache at `path`
+pub fn check_cache(path: &Path) -> CheckResult {
+    let result = Cache::open(path).and_then(|cache| cache.integrity_check());
+    match result {
+        Ok(problems) if problems.is_empty() => CheckResult::Healthy,
+        Ok(problems) => CheckResult::Corrupt(problems),
+        Err(e) => CheckResult::Unreadable(format!("{:#}", e)),
+    }
+}
+
+/// How a corrupt cache was repaired
+#[derive(Debug)]
+pub enum RepairResult {
+    /// Rebuilding the indexes was enough
+    Reindexed,
+    /// The database was recreated and refilled from the GitHub API
+    Rebuilt(RefreshResult),
+}
+
+impl std::fmt::Display for RepairResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepairResult::Reindexed => write!(f, "Repaired cache by rebuilding its indexes"),
+            RepairResult::Rebuilt(result) => write!(f, "Rebuilt cache from GitHub: {}", result),
+        }
+    }
+}
+
+/// Repair the cache at `path`
+/// Tries `REINDEX` first, then falls back to recreating the database from the API,
+/// keeping whatever usage data can still be read
+pub async fn repair_cache(path: &Path, token: String, quiet: bool) -> Result<RepairResult> {
+    if let Ok(cache) = Cache::open(path)
+        && cache.reindex().is_ok()
+        && cache.integrity_check().is_ok_and(|problems| problems.is_empty())
+    {
+        return Ok(RepairResult::Reindexed);
+    }
+
+    // Salvage usage data before throwing the old database away
+    let salvaged = Cache::open(path)
+        .ok()
+        .map(|cache| (cache.load_orgs().ok(), cache.load_repos().ok()));
+
+    for suffix in ["", "-wal", "-shm"] {
+        let file = PathBuf::from(format!("{}{}", path.display(), suffix));
+        if file.exists() {
+            std::fs::remove_file(&file)
+                .with_context(|| format!("Failed to remove {}", file.display()))?;
+        }
+    }
+
+    let cache = Cache::open(path)?;
+    if let Some((orgs, repos)) = salvaged {
+        cache.upsert_orgs(&orgs.unwrap_or_default())?;
+        cache.upsert_repos(&repos.unwrap_or_default())?;
+    }
+
+    let result = refresh_cache(token, &cache, quiet).await?;
+    Ok(RepairResult::Rebuilt(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_unreadable_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.db");
+        std::fs::write(&path, b"this is not a sqlite database, just some junk bytes").unwrap();
+
+        assert!(matches!(check_cache(&path), CheckResult::Unreadable(_)));
+    }
+
+    #[test]
+    fn test_check_healthy_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.db");
+
+        assert!(matches!(check_cache(&path), CheckResult::Healthy));
+    }
+}
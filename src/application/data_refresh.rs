@@ -1,11 +1,14 @@
+use crate::domain::{Org, Repo};
 use crate::infrastructure::{Cache, GitHubClient};
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use indicatif::{ProgressBar, ProgressStyle};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
 
 /// Refresh the cache by fetching all orgs and repos from GitHub
-pub async fn refresh_cache(token: String, quiet: bool) -> Result<RefreshResult> {
+pub async fn refresh_cache(token: String, cache: &Cache, quiet: bool) -> Result<RefreshResult> {
     let client = GitHubClient::new(token)?;
-    let cache = Cache::open()?;
 
     let spinner = if !quiet {
         let pb = ProgressBar::new(3);
@@ -49,6 +52,7 @@ pub async fn refresh_cache(token: String, quiet: bool) -> Result<RefreshResult>
         pb.inc(1);
     }
     cache.store_repos(&repos)?;
+    cache.set_last_refresh(Utc::now())?;
 
     if let Some(pb) = spinner {
         pb.set_style(ProgressStyle::default_bar()
@@ -69,6 +73,58 @@ pub async fn refresh_cache(token: String, quiet: bool) -> Result<RefreshResult>
     })
 }
 
+/// Check whether cached data is missing or older than `ttl`
+/// A zero `ttl` disables automatic refreshes
+pub fn needs_refresh(last_refresh: Option<DateTime<Utc>>, has_data: bool, ttl: Duration) -> bool {
+    if ttl.is_zero() {
+        return false;
+    }
+    match last_refresh {
+        Some(at) if has_data => (Utc::now() - at).to_std().map(|age| age > ttl).unwrap_or(false),
+        _ => true,
+    }
+}
+
+/// Progress events sent by a background refresh
+#[derive(Debug)]
+pub enum RefreshEvent {
+    /// All organizations were fetched
+    Orgs(Vec<Org>),
+    /// Another page of repositories was fetched
+    Repos(Vec<Repo>),
+    /// Every page was fetched successfully
+    Finished,
+    /// The refresh stopped with an error
+    Failed(String),
+}
+
+/// Fetch all orgs and repos on the tokio runtime without blocking the caller
+/// Results are streamed over the returned channel as each page arrives
+pub fn spawn_background_refresh(token: String) -> Receiver<RefreshEvent> {
+    let (tx, rx) = mpsc::channel();
+
+    tokio::spawn(async move {
+        let result: Result<()> = async {
+            let client = GitHubClient::new(token)?;
+            let orgs = client.fetch_orgs().await?;
+            let _ = tx.send(RefreshEvent::Orgs(orgs));
+            client
+                .fetch_repos_paged(|page| {
+                    let _ = tx.send(RefreshEvent::Repos(page.to_vec()));
+                })
+                .await
+        }
+        .await;
+
+        let _ = tx.send(match result {
+            Ok(()) => RefreshEvent::Finished,
+            Err(e) => RefreshEvent::Failed(format!("{:#}", e)),
+        });
+    });
+
+    rx
+}
+
 #[derive(Debug)]
 pub struct RefreshResult {
     pub orgs_fetched: usize,
@@ -84,3 +140,24 @@ impl std::fmt::Display for RefreshResult {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    #[test]
+    fn test_needs_refresh() {
+        let fresh = Some(Utc::now() - chrono::Duration::hours(1));
+        let stale = Some(Utc::now() - chrono::Duration::hours(25));
+
+        assert!(!needs_refresh(fresh, true, DAY));
+        assert!(needs_refresh(stale, true, DAY));
+        assert!(needs_refresh(None, true, DAY));
+        // An empty cache is refreshed regardless of when it was last refreshed
+        assert!(needs_refresh(fresh, false, DAY));
+        // A zero TTL disables automatic refreshes
+        assert!(!needs_refresh(None, false, Duration::ZERO));
+    }
+}
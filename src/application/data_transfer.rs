@@ -0,0 +1,403 @@
+use crate::config::{ExportFormat, ImportMode};
+use crate::domain::{Org, Repo};
+use crate::infrastructure::Cache;
+use crate::tui::matcher::RepoMatcher;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Current version of the export schema
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Versioned snapshot of the cache, including usage data
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportData {
+    /// Schema version (exports made before versioning was added read as 0)
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exported_at: Option<DateTime<Utc>>,
+    pub orgs: Vec<Org>,
+    pub repos: Vec<Repo>,
+}
+
+impl ExportData {
+    /// Parse an export document, rejecting versions newer than we understand
+    pub fn from_json(json: &str) -> Result<Self> {
+        let data: ExportData =
+            serde_json::from_str(json).context("Failed to parse export data")?;
+
+        if data.version > EXPORT_VERSION {
+            anyhow::bail!(
+                "Export version {} is newer than supported version {}. Please upgrade gg.",
+                data.version,
+                EXPORT_VERSION
+            );
+        }
+
+        Ok(data)
+    }
+}
+
+/// Filters applied to repos before exporting
+#[derive(Debug, Default, Clone)]
+pub struct ExportFilter {
+    pub org: Option<String>,
+    pub language: Option<String>,
+    pub private: bool,
+    pub query: Option<String>,
+}
+
+impl ExportFilter {
+    /// Check the non-fuzzy filters against a single repo
+    fn matches(&self, repo: &Repo) -> bool {
+        if let Some(org) = &self.org
+            && !repo.owner_login.eq_ignore_ascii_case(org)
+        {
+            return false;
+        }
+        if let Some(language) = &self.language
+            && !repo
+                .language
+                .as_deref()
+                .is_some_and(|l| l.eq_ignore_ascii_case(language))
+        {
+            return false;
+        }
+        !self.private || repo.private
+    }
+
+    /// Filter orgs and repos, ordering repos by match rank when a query is given
+    pub fn apply(&self, orgs: Vec<Org>, repos: Vec<Repo>) -> (Vec<Org>, Vec<Repo>) {
+        let orgs = orgs
+            .into_iter()
+            .filter(|o| self.org.as_ref().is_none_or(|org| o.login.eq_ignore_ascii_case(org)))
+            .collect();
+
+        let repos: Vec<Repo> = repos.into_iter().filter(|r| self.matches(r)).collect();
+
+        let repos = match self.query.as_deref() {
+            Some(query) if !query.is_empty() => {
+                let mut matcher = RepoMatcher::new(repos, Vec::new());
+                matcher.update_pattern(query.to_string());
+                matcher.finish();
+                matcher
+                    .matches_sorted()
+                    .into_iter()
+                    .map(|item| item.repo.clone())
+                    .collect()
+            }
+            _ => repos,
+        };
+
+        (orgs, repos)
+    }
+}
+
+/// Export cached orgs and repos that pass the given filter
+pub fn export_data(cache: &Cache, filter: &ExportFilter) -> Result<ExportData> {
+    let (orgs, repos) = filter.apply(cache.load_orgs()?, cache.load_repos()?);
+
+    Ok(ExportData {
+        version: EXPORT_VERSION,
+        exported_at: Some(Utc::now()),
+        orgs,
+        repos,
+    })
+}
+
+/// Write export data in the requested format
+/// Only the JSON format includes orgs; the other formats are one row per repo
+pub fn write_export(data: &ExportData, format: ExportFormat, out: &mut impl Write) -> Result<()> {
+    match format {
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, data)?;
+            writeln!(out)?;
+        }
+        ExportFormat::Ndjson => {
+            for repo in &data.repos {
+                serde_json::to_writer(&mut *out, repo)?;
+                writeln!(out)?;
+            }
+        }
+        ExportFormat::Csv => {
+            writeln!(
+                out,
+                "full_name,owner,name,private,language,default_branch,description,access_count,last_accessed_at"
+            )?;
+            for repo in &data.repos {
+                let fields = [
+                    repo.full_name.clone(),
+                    repo.owner_login.clone(),
+                    repo.name.clone(),
+                    repo.private.to_string(),
+                    repo.language.clone().unwrap_or_default(),
+                    repo.default_branch.clone().unwrap_or_default(),
+                    repo.description.clone().unwrap_or_default(),
+                    repo.access_count.to_string(),
+                    repo.last_accessed_at
+                        .map(|d| d.to_rfc3339())
+                        .unwrap_or_default(),
+                ];
+                let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                writeln!(out, "{}", row.join(","))?;
+            }
+        }
+        ExportFormat::Names => {
+            for repo in &data.repos {
+                writeln!(out, "{}", repo.full_name)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Import an export document into the cache
+/// A path of `-` reads from stdin
+pub fn import_data(cache: &Cache, path: &Path, mode: ImportMode) -> Result<ImportResult> {
+    let json = if path == Path::new("-") {
+        let mut buf = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buf)
+            .context("Failed to read export data from stdin")?;
+        buf
+    } else {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+    };
+
+    let data = ExportData::from_json(&json)?;
+
+    let (orgs, repos) = match mode {
+        ImportMode::Replace => {
+            cache.replace_orgs_and_repos(&data.orgs, &data.repos)?;
+            (data.orgs, data.repos)
+        }
+        ImportMode::Merge => {
+            let orgs = merge_orgs(cache.load_orgs()?, data.orgs);
+            let repos = merge_repos(cache.load_repos()?, data.repos);
+            cache.upsert_orgs(&orgs)?;
+            cache.upsert_repos(&repos)?;
+            (orgs, repos)
+        }
+    };
+
+    Ok(ImportResult {
+        orgs_imported: orgs.len(),
+        repos_imported: repos.len(),
+        mode,
+    })
+}
+
+/// Combine usage data, keeping the most recent access and the highest count
+fn merge_usage(
+    local: (Option<DateTime<Utc>>, u32),
+    imported: (Option<DateTime<Utc>>, u32),
+) -> (Option<DateTime<Utc>>, u32) {
+    (local.0.max(imported.0), local.1.max(imported.1))
+}
+
+/// Merge imported orgs with the ones already cached (matched by login)
+fn merge_orgs(existing: Vec<Org>, imported: Vec<Org>) -> Vec<Org> {
+    let existing: HashMap<String, Org> = existing
+        .into_iter()
+        .map(|org| (org.login.clone(), org))
+        .collect();
+
+    imported
+        .into_iter()
+        .map(|mut org| {
+            if let Some(local) = existing.get(&org.login) {
+                (org.last_accessed_at, org.access_count) = merge_usage(
+                    (local.last_accessed_at, local.access_count),
+                    (org.last_accessed_at, org.access_count),
+                );
+            }
+            org
+        })
+        .collect()
+}
+
+/// Merge imported repos with the ones already cached (matched by full_name)
+fn merge_repos(existing: Vec<Repo>, imported: Vec<Repo>) -> Vec<Repo> {
+    let existing: HashMap<String, Repo> = existing
+        .into_iter()
+        .map(|repo| (repo.full_name.clone(), repo))
+        .collect();
+
+    imported
+        .into_iter()
+        .map(|mut repo| {
+            if let Some(local) = existing.get(&repo.full_name) {
+                (repo.last_accessed_at, repo.access_count) = merge_usage(
+                    (local.last_accessed_at, local.access_count),
+                    (repo.last_accessed_at, repo.access_count),
+                );
+            }
+            repo
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub struct ImportResult {
+    pub orgs_imported: usize,
+    pub repos_imported: usize,
+    pub mode: ImportMode,
+}
+
+impl std::fmt::Display for ImportResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let verb = match self.mode {
+            ImportMode::Merge => "Merged",
+            ImportMode::Replace => "Imported",
+        };
+        write!(
+            f,
+            "{} {} org(s) and {} repo(s)",
+            verb, self.orgs_imported, self.repos_imported
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(full_name: &str, access_count: u32, days_ago: Option<i64>) -> Repo {
+        let (owner, name) = full_name.split_once('/').unwrap();
+        let mut repo = Repo::new(
+            1,
+            name.to_string(),
+            full_name.to_string(),
+            1,
+            owner.to_string(),
+            false,
+            None,
+            None,
+            None,
+        );
+        repo.access_count = access_count;
+        repo.last_accessed_at = days_ago.map(|d| Utc::now() - chrono::Duration::days(d));
+        repo
+    }
+
+    fn export(repos: Vec<Repo>) -> ExportData {
+        ExportData {
+            version: EXPORT_VERSION,
+            exported_at: None,
+            orgs: Vec::new(),
+            repos,
+        }
+    }
+
+    #[test]
+    fn test_filter_by_org_language_and_private() {
+        let mut rust = repo("revsys/tool", 0, None);
+        rust.language = Some("Rust".to_string());
+        rust.private = true;
+        let mut python = repo("revsys/site", 0, None);
+        python.language = Some("Python".to_string());
+        let other = repo("frankwiles/gg", 0, None);
+
+        let filter = ExportFilter {
+            org: Some("REVSYS".to_string()),
+            language: Some("rust".to_string()),
+            private: true,
+            query: None,
+        };
+        let (_, repos) = filter.apply(Vec::new(), vec![rust, python, other]);
+
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].full_name, "revsys/tool");
+    }
+
+    #[test]
+    fn test_filter_by_fuzzy_query() {
+        let filter = ExportFilter {
+            query: Some("gg".to_string()),
+            ..Default::default()
+        };
+        let (_, repos) = filter.apply(
+            Vec::new(),
+            vec![repo("revsys/site", 0, None), repo("frankwiles/gg", 0, None)],
+        );
+
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].full_name, "frankwiles/gg");
+    }
+
+    #[test]
+    fn test_write_csv_escapes_fields() {
+        let mut r = repo("org/a", 3, None);
+        r.description = Some("Fast, \"fuzzy\" finder".to_string());
+
+        let mut out = Vec::new();
+        write_export(&export(vec![r]), ExportFormat::Csv, &mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+
+        let row = csv.lines().nth(1).unwrap();
+        assert_eq!(row, "org/a,org,a,false,,,\"Fast, \"\"fuzzy\"\" finder\",3,");
+    }
+
+    #[test]
+    fn test_write_names_and_ndjson() {
+        let data = export(vec![repo("org/a", 0, None), repo("org/b", 0, None)]);
+
+        let mut out = Vec::new();
+        write_export(&data, ExportFormat::Names, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "org/a\norg/b\n");
+
+        let mut out = Vec::new();
+        write_export(&data, ExportFormat::Ndjson, &mut out).unwrap();
+        let lines: Vec<Repo> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines, data.repos);
+    }
+
+    #[test]
+    fn test_parse_unversioned_export() {
+        let json = r#"{"orgs":[],"repos":[{"id":1,"name":"gg","full_name":"frankwiles/gg","owner_id":2,"owner_login":"frankwiles","private":false}]}"#;
+        let data = ExportData::from_json(json).unwrap();
+        assert_eq!(data.version, 0);
+        assert_eq!(data.repos.len(), 1);
+        assert_eq!(data.repos[0].access_count, 0);
+    }
+
+    #[test]
+    fn test_reject_newer_export_version() {
+        let json = format!(r#"{{"version":{},"orgs":[],"repos":[]}}"#, EXPORT_VERSION + 1);
+        assert!(ExportData::from_json(&json).is_err());
+    }
+
+    #[test]
+    fn test_merge_repos_keeps_highest_usage() {
+        let existing = vec![repo("org/a", 5, Some(1)), repo("org/b", 1, None)];
+        let imported = vec![repo("org/a", 2, Some(10)), repo("org/b", 7, Some(3))];
+
+        let merged = merge_repos(existing, imported);
+
+        assert_eq!(merged[0].access_count, 5);
+        assert_eq!(
+            merged[0].last_accessed_at.unwrap().date_naive(),
+            (Utc::now() - chrono::Duration::days(1)).date_naive()
+        );
+        assert_eq!(merged[1].access_count, 7);
+        assert!(merged[1].last_accessed_at.is_some());
+    }
+}
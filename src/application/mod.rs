@@ -1,5 +1,27 @@
+pub mod action_logs;
+pub mod actions_dashboard;
+pub mod actions_stats;
+pub mod artifacts;
+pub mod data_maintenance;
 pub mod data_refresh;
+pub mod data_transfer;
+pub mod notify;
+pub mod run_control;
+pub mod run_selection;
 pub mod watch_action;
+pub mod watch_checks;
 
-pub use data_refresh::refresh_cache;
-pub use watch_action::watch_action;
+pub use action_logs::{action_logs, LogOptions};
+pub use actions_dashboard::{dashboard_repos, spawn_dashboard_poll, DashboardEvent};
+pub use actions_stats::{actions_stats, StatsOptions};
+pub use artifacts::{download_artifacts, format_size, list_artifacts};
+pub use data_maintenance::{check_cache, repair_cache, CheckResult};
+pub use data_refresh::{needs_refresh, refresh_cache, spawn_background_refresh, RefreshEvent};
+pub use data_transfer::{export_data, import_data, write_export, ExportFilter};
+pub use notify::{notify, shell_command, shell_quote, Completion, NotifyOptions};
+pub use run_control::{cancel_action, dispatch_workflow, rerun_action, DispatchOptions};
+pub use run_selection::RunQuery;
+pub use watch_action::{
+    format_duration, list_actions, render_run_table, watch_action, WatchOptions, WATCH_ERROR_EXIT_CODE,
+};
+pub use watch_checks::{watch_checks, ChecksOptions};
@@ -0,0 +1,234 @@
+use crate::config::TerminalNotification;
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::io::Write;
+use std::process::Command;
+use std::time::Duration;
+
+/// Where to send a notification when a watched run finishes
+#[derive(Debug, Clone, Default)]
+pub struct NotifyOptions {
+    /// Ring the terminal bell
+    pub bell: bool,
+    /// Desktop notification through the terminal's OSC escape sequences
+    pub terminal: Option<TerminalNotification>,
+    /// Shell command to run, with `{name}`, `{branch}`, `{conclusion}` and `{url}` placeholders
+    pub command: Option<String>,
+    /// URL to POST the completion to as JSON
+    pub webhook: Option<String>,
+}
+
+/// A watched run or check suite that reached a conclusion
+#[derive(Debug, Clone, Serialize)]
+pub struct Completion {
+    pub name: String,
+    pub branch: String,
+    pub conclusion: String,
+    pub url: String,
+}
+
+impl Completion {
+    fn title(&self) -> String {
+        format!("{} {}", self.name, self.conclusion)
+    }
+}
+
+/// Send `completion` to every configured sink
+/// Returns the errors of the sinks that failed, so one broken sink doesn't stop the others
+pub async fn notify(options: &NotifyOptions, completion: &Completion) -> Vec<anyhow::Error> {
+    let mut errors = Vec::new();
+
+    let mut escapes = String::new();
+    if options.bell {
+        escapes.push('\x07');
+    }
+    if let Some(protocol) = options.terminal {
+        escapes.push_str(&osc_sequence(protocol, completion));
+    }
+    if !escapes.is_empty() {
+        // stderr, so escape sequences never end up in piped output
+        let mut stderr = std::io::stderr().lock();
+        if let Err(e) = stderr.write_all(escapes.as_bytes()).and_then(|_| stderr.flush()) {
+            errors.push(anyhow::Error::new(e).context("Failed to write terminal notification"));
+        }
+    }
+
+    if let Some(template) = &options.command
+        && let Err(e) = run_command(template, completion)
+    {
+        errors.push(e);
+    }
+
+    if let Some(url) = &options.webhook
+        && let Err(e) = post_webhook(url, completion).await
+    {
+        errors.push(e);
+    }
+
+    errors
+}
+
+/// Escape sequence asking the terminal to show a desktop notification
+fn osc_sequence(protocol: TerminalNotification, completion: &Completion) -> String {
+    // Control characters would end the sequence early, and `;` separates OSC 777 fields
+    let clean = |s: &str| -> String {
+        s.chars()
+            .filter(|c| !c.is_control())
+            .map(|c| if c == ';' { ',' } else { c })
+            .collect()
+    };
+
+    match protocol {
+        TerminalNotification::Osc9 => format!("\x1b]9;gg: {}\x07", clean(&completion.title())),
+        TerminalNotification::Osc777 => format!(
+            "\x1b]777;notify;gg: {};{}\x07",
+            clean(&completion.title()),
+            clean(&completion.url)
+        ),
+    }
+}
+
+/// Quote a value for use as a single shell word
+pub fn shell_quote(value: &str) -> String {
+    if cfg!(windows) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// Fill in the command template placeholders with shell-quoted values
+fn expand_command(template: &str, completion: &Completion) -> String {
+    template
+        .replace("{name}", &shell_quote(&completion.name))
+        .replace("{branch}", &shell_quote(&completion.branch))
+        .replace("{conclusion}", &shell_quote(&completion.conclusion))
+        .replace("{url}", &shell_quote(&completion.url))
+}
+
+/// `command` run through the platform's shell
+pub fn shell_command(command: &str) -> Command {
+    let mut shell = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    shell.arg(command);
+    shell
+}
+
+/// Run the user's command through the shell
+/// The values are also available as `GG_NAME`, `GG_BRANCH`, `GG_CONCLUSION` and `GG_URL`
+fn run_command(template: &str, completion: &Completion) -> Result<()> {
+    let command = expand_command(template, completion);
+    let status = shell_command(&command)
+        .env("GG_NAME", &completion.name)
+        .env("GG_BRANCH", &completion.branch)
+        .env("GG_CONCLUSION", &completion.conclusion)
+        .env("GG_URL", &completion.url)
+        .status()
+        .with_context(|| format!("Failed to run notification command: {}", command))?;
+
+    if !status.success() {
+        bail!("Notification command exited with {}: {}", status, command);
+    }
+    Ok(())
+}
+
+/// POST the completion as JSON
+async fn post_webhook(url: &str, completion: &Completion) -> Result<()> {
+    let response = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?
+        .post(url)
+        .json(completion)
+        .send()
+        .await
+        .with_context(|| format!("Failed to send webhook to {}", url))?;
+
+    if !response.status().is_success() {
+        bail!("Webhook {} responded with {}", url, response.status());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn completion() -> Completion {
+        Completion {
+            name: "CI".to_string(),
+            branch: "it's/a;branch".to_string(),
+            conclusion: "failure".to_string(),
+            url: "https://github.com/octocat/hello/actions/runs/42".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_osc_sequences() {
+        assert_eq!(
+            osc_sequence(TerminalNotification::Osc9, &completion()),
+            "\x1b]9;gg: CI failure\x07"
+        );
+        assert_eq!(
+            osc_sequence(TerminalNotification::Osc777, &completion()),
+            "\x1b]777;notify;gg: CI failure;https://github.com/octocat/hello/actions/runs/42\x07"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_gets_quoted_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out.txt");
+        let template = format!(
+            "printf '%s|%s|%s' {{branch}} {{conclusion}} \"$GG_NAME\" > {}",
+            out.display()
+        );
+
+        run_command(&template, &completion()).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            "it's/a;branch|failure|CI"
+        );
+        assert!(run_command("exit 3", &completion()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_posts_json() {
+        let mut server = mockito::Server::new_async().await;
+        let hook = server
+            .mock("POST", "/hook")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "name": "CI",
+                "branch": "it's/a;branch",
+                "conclusion": "failure",
+                "url": "https://github.com/octocat/hello/actions/runs/42"
+            })))
+            .with_status(204)
+            .create_async()
+            .await;
+        let failing = server.mock("POST", "/broken").with_status(500).create_async().await;
+
+        let options = NotifyOptions {
+            webhook: Some(format!("{}/hook", server.url())),
+            ..Default::default()
+        };
+        assert!(notify(&options, &completion()).await.is_empty());
+
+        let options = NotifyOptions {
+            webhook: Some(format!("{}/broken", server.url())),
+            ..Default::default()
+        };
+        assert_eq!(notify(&options, &completion()).await.len(), 1);
+
+        hook.assert_async().await;
+        failing.assert_async().await;
+    }
+}
@@ -0,0 +1,126 @@
+use super::run_selection::{select_run, RunQuery};
+use crate::git::{get_current_branch, get_github_repo, get_repo_root};
+use crate::infrastructure::{load_workflows, GitHubClient, WorkflowFile, WorkflowRun};
+use anyhow::{bail, Result};
+
+/// Re-run a completed workflow run (all jobs, or only the failed ones)
+pub async fn rerun_action(token: String, query: &RunQuery, failed_only: bool) -> Result<WorkflowRun> {
+    let repo = get_github_repo()?;
+    let client = GitHubClient::new(token)?;
+    let run = select_run(&client, &repo, query).await?;
+
+    if run.is_running() {
+        bail!("{} #{} is still running, cancel it first or wait for it to finish", run.name, run.id);
+    }
+
+    client
+        .rerun_workflow_run(&repo.owner, &repo.name, run.id, failed_only)
+        .await?;
+    Ok(run)
+}
+
+/// Cancel a queued or in-progress workflow run
+pub async fn cancel_action(token: String, query: &RunQuery) -> Result<WorkflowRun> {
+    let repo = get_github_repo()?;
+    let client = GitHubClient::new(token)?;
+    let run = select_run(&client, &repo, query).await?;
+
+    if !run.is_running() {
+        bail!("{} #{} has already completed", run.name, run.id);
+    }
+
+    client.cancel_workflow_run(&repo.owner, &repo.name, run.id).await?;
+    Ok(run)
+}
+
+/// Options for `gg actions dispatch`
+#[derive(Debug, Clone)]
+pub struct DispatchOptions {
+    /// Workflow file name (with or without extension) or workflow `name:`
+    pub workflow: String,
+    /// Branch or tag to run on (defaults to the current branch)
+    pub git_ref: Option<String>,
+    /// `-f key=value` inputs
+    pub fields: Vec<(String, String)>,
+}
+
+/// A dispatched workflow
+#[derive(Debug)]
+pub struct DispatchResult {
+    pub workflow: WorkflowFile,
+    pub git_ref: String,
+}
+
+impl std::fmt::Display for DispatchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Dispatched {} on {}",
+            self.workflow.name.as_deref().unwrap_or(&self.workflow.file_name),
+            self.git_ref
+        )
+    }
+}
+
+/// Trigger a `workflow_dispatch` run, validating the inputs against the local workflow file first
+pub async fn dispatch_workflow(token: String, options: &DispatchOptions) -> Result<DispatchResult> {
+    let repo = get_github_repo()?;
+    let workflow = find_workflow(load_workflows(&get_repo_root()?), &options.workflow)?;
+    let inputs = workflow.validate_inputs(&options.fields)?;
+    let git_ref = match &options.git_ref {
+        Some(git_ref) => git_ref.clone(),
+        None => get_current_branch()?,
+    };
+
+    let client = GitHubClient::new(token)?;
+    client
+        .dispatch_workflow(&repo.owner, &repo.name, &workflow.file_name, &git_ref, inputs)
+        .await?;
+
+    Ok(DispatchResult { workflow, git_ref })
+}
+
+/// Find a dispatchable workflow by file name or `name:`
+fn find_workflow(workflows: Vec<WorkflowFile>, query: &str) -> Result<WorkflowFile> {
+    let dispatchable: Vec<&str> = workflows
+        .iter()
+        .filter(|workflow| workflow.dispatch_inputs.is_some())
+        .map(|workflow| workflow.file_name.as_str())
+        .collect();
+    let available = if dispatchable.is_empty() {
+        "no workflows with a workflow_dispatch trigger found in .github/workflows".to_string()
+    } else {
+        format!("dispatchable workflows: {}", dispatchable.join(", "))
+    };
+
+    match workflows.iter().find(|workflow| workflow.matches(query)) {
+        Some(workflow) if workflow.dispatch_inputs.is_none() => bail!(
+            "Workflow {} has no workflow_dispatch trigger ({})",
+            workflow.file_name,
+            available
+        ),
+        Some(workflow) => Ok(workflow.clone()),
+        None => bail!("Workflow '{}' not found ({})", query, available),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_workflow() {
+        let workflows = vec![
+            WorkflowFile::parse("ci.yml", "name: CI\non: [push]").unwrap(),
+            WorkflowFile::parse("deploy.yml", "name: Deploy\non: workflow_dispatch").unwrap(),
+        ];
+
+        assert_eq!(find_workflow(workflows.clone(), "Deploy").unwrap().file_name, "deploy.yml");
+
+        let not_dispatchable = find_workflow(workflows.clone(), "ci").unwrap_err().to_string();
+        assert!(not_dispatchable.contains("no workflow_dispatch trigger"));
+        assert!(not_dispatchable.contains("deploy.yml"));
+
+        assert!(find_workflow(workflows, "release").is_err());
+    }
+}
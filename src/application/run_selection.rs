@@ -0,0 +1,153 @@
+use crate::git::{get_current_branch, get_head_sha, GitHubRepo};
+use crate::infrastructure::{GitHubClient, RunFilter, WorkflowRun};
+use anyhow::Result;
+
+/// Number of runs fetched when picking a run
+const SELECT_LIMIT: u8 = 30;
+
+/// How to find the workflow run(s) to act on
+#[derive(Debug, Clone, Default)]
+pub struct RunQuery {
+    /// Explicit run id; the filters are ignored when set
+    pub run_id: Option<u64>,
+    /// Workflow file name (e.g. `ci.yml`) or workflow name
+    pub workflow: Option<String>,
+    /// Defaults to the current branch
+    pub branch: Option<String>,
+    pub event: Option<String>,
+    /// Defaults to the local HEAD commit when selecting a single run
+    pub head_sha: Option<String>,
+    pub status: Option<String>,
+}
+
+impl RunQuery {
+    /// API filter with the branch defaulted to the current branch
+    fn filter(&self) -> Result<RunFilter> {
+        let branch = match &self.branch {
+            Some(branch) => branch.clone(),
+            None => get_current_branch()?,
+        };
+        Ok(RunFilter {
+            workflow: self.workflow.clone(),
+            branch: Some(branch),
+            event: self.event.clone(),
+            head_sha: self.head_sha.clone(),
+            status: self.status.clone(),
+        })
+    }
+}
+
+/// Pick the workflow run to act on
+/// An explicit run id wins; otherwise the running (or most recent) run matching the query,
+/// preferring runs for the local HEAD commit unless a commit was given
+pub async fn select_run(client: &GitHubClient, repo: &GitHubRepo, query: &RunQuery) -> Result<WorkflowRun> {
+    if let Some(id) = query.run_id {
+        return client.fetch_workflow_run(&repo.owner, &repo.name, id).await;
+    }
+
+    let filter = query.filter()?;
+    let mut runs = Vec::new();
+
+    // HEAD may not be pushed yet, so fall back to any commit on the branch
+    if filter.head_sha.is_none()
+        && let Ok(sha) = get_head_sha()
+    {
+        let head_filter = RunFilter {
+            head_sha: Some(sha),
+            ..filter.clone()
+        };
+        runs = client
+            .fetch_workflow_runs(&repo.owner, &repo.name, &head_filter, SELECT_LIMIT)
+            .await?;
+    }
+    if runs.is_empty() {
+        runs = client
+            .fetch_workflow_runs(&repo.owner, &repo.name, &filter, SELECT_LIMIT)
+            .await?;
+    }
+
+    pick_run(runs).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No workflow runs found for {} in {}/{}",
+            describe(&filter),
+            repo.owner,
+            repo.name
+        )
+    })
+}
+
+/// Recent workflow runs matching the query, newest first
+pub async fn list_runs(client: &GitHubClient, repo: &GitHubRepo, query: &RunQuery, limit: u8) -> Result<Vec<WorkflowRun>> {
+    if let Some(id) = query.run_id {
+        return Ok(vec![client.fetch_workflow_run(&repo.owner, &repo.name, id).await?]);
+    }
+    client
+        .fetch_workflow_runs(&repo.owner, &repo.name, &query.filter()?, limit)
+        .await
+}
+
+/// The first queued or in-progress run, otherwise the most recent one
+fn pick_run(runs: Vec<WorkflowRun>) -> Option<WorkflowRun> {
+    let running = runs.iter().position(|run| run.is_running()).unwrap_or(0);
+    runs.into_iter().nth(running)
+}
+
+/// Human readable summary of a filter for error messages
+fn describe(filter: &RunFilter) -> String {
+    let mut parts = Vec::new();
+    if let Some(branch) = &filter.branch {
+        parts.push(format!("branch '{}'", branch));
+    }
+    if let Some(workflow) = &filter.workflow {
+        parts.push(format!("workflow '{}'", workflow));
+    }
+    if let Some(event) = &filter.event {
+        parts.push(format!("event '{}'", event));
+    }
+    if let Some(sha) = &filter.head_sha {
+        parts.push(format!("commit {}", &sha[..sha.len().min(7)]));
+    }
+    if let Some(status) = &filter.status {
+        parts.push(format!("status '{}'", status));
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(id: u64, status: &str) -> WorkflowRun {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "name": "CI", "status": status, "conclusion": null,
+            "head_branch": "main", "head_sha": "abc123", "event": "push",
+            "html_url": "", "created_at": "2026-01-01T10:00:00Z", "updated_at": "2026-01-01T10:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pick_run_prefers_running() {
+        let picked = pick_run(vec![run(3, "completed"), run(2, "in_progress"), run(1, "queued")]);
+        assert_eq!(picked.unwrap().id, 2);
+
+        let picked = pick_run(vec![run(3, "completed"), run(2, "completed")]);
+        assert_eq!(picked.unwrap().id, 3);
+
+        assert!(pick_run(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_describe_filter() {
+        let filter = RunFilter {
+            workflow: Some("ci.yml".to_string()),
+            branch: Some("feat/a+b".to_string()),
+            head_sha: Some("0123456789abcdef".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            describe(&filter),
+            "branch 'feat/a+b', workflow 'ci.yml', commit 0123456"
+        );
+    }
+}
@@ -1,12 +1,41 @@
-use crate::git::{get_current_branch, get_github_repo};
-use crate::infrastructure::GitHubClient;
+use super::notify::Completion;
+use super::run_selection::{list_runs, select_run, RunQuery};
+use crate::git::get_github_repo;
+use crate::infrastructure::{GitHubClient, WorkflowJob, WorkflowRun};
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use crossterm::{
+    cursor::MoveUp,
+    execute,
+    style::{Color, Stylize},
+    terminal::{Clear, ClearType},
+};
 use indicatif::{ProgressBar, ProgressStyle};
+use std::future::Future;
+use std::io::{self, IsTerminal, Write};
+use std::time::Duration;
 
-/// Find and open the most recent or running GitHub Action workflow for the current repo/branch
-pub async fn watch_action(token: String, quiet: bool) -> Result<ActionResult> {
+/// Exit code when the run couldn't be watched, e.g. because the GitHub API kept failing
+pub const WATCH_ERROR_EXIT_CODE: i32 = 4;
+
+/// Times a poll is tried before a watch gives up, so a network blip doesn't end it
+const POLL_ATTEMPTS: u32 = 3;
+
+/// Options for `gg watch action`
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// Which run to watch
+    pub run: RunQuery,
+    /// Only find the run and return it, without following it until completion
+    pub open_only: bool,
+    /// Time between polls while following a run
+    pub interval: Duration,
+}
+
+/// Find the most recent or running GitHub Action workflow for the current repo/branch
+/// and follow it in the terminal until it completes (unless `open_only` is set)
+pub async fn watch_action(token: String, options: &WatchOptions, quiet: bool) -> Result<ActionResult> {
     let repo = get_github_repo()?;
-    let branch = get_current_branch()?;
 
     let spinner = if !quiet {
         let pb = ProgressBar::new(2);
@@ -29,28 +58,259 @@ pub async fn watch_action(token: String, quiet: bool) -> Result<ActionResult> {
         pb.inc(1);
     }
 
-    let workflow_run = client
-        .fetch_workflow_runs(&repo.owner, &repo.name, Some(&branch))
-        .await?;
+    let mut run = select_run(&client, &repo, &options.run).await?;
 
     if let Some(pb) = spinner {
-        pb.finish_with_message("Found workflow run");
+        if options.open_only {
+            pb.finish_with_message("Found workflow run");
+        } else {
+            pb.finish_and_clear();
+        }
     }
 
-    match workflow_run {
-        Some(run) => Ok(ActionResult {
-            workflow_name: run.name.clone(),
-            status: run.status.clone(),
-            conclusion: run.conclusion.clone(),
-            branch: run.head_branch.clone(),
-            url: run.html_url.clone(),
-        }),
-        None => Err(anyhow::anyhow!(
-            "No workflow runs found for branch '{}' in {}/{}",
-            branch,
-            repo.owner,
-            repo.name
-        )),
+    if !options.open_only {
+        let mut display = RunDisplay::new(quiet);
+        loop {
+            let jobs = retry(options.interval, || {
+                client.fetch_run_jobs(&repo.owner, &repo.name, run.id)
+            })
+            .await?;
+            display.render(&run, &jobs)?;
+
+            if !run.is_running() {
+                break;
+            }
+
+            tokio::time::sleep(options.interval).await;
+            run = retry(options.interval, || {
+                client.fetch_workflow_run(&repo.owner, &repo.name, run.id)
+            })
+            .await?;
+        }
+    }
+
+    Ok(ActionResult::from(&run))
+}
+
+/// Run `poll` up to `POLL_ATTEMPTS` times, waiting `interval` after each failure
+async fn retry<T, F, Fut>(interval: Duration, mut poll: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match poll().await {
+            Err(_) if attempt < POLL_ATTEMPTS => {
+                attempt += 1;
+                tokio::time::sleep(interval).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Recent workflow runs for the current repo matching the query, newest first
+pub async fn list_actions(token: String, query: &RunQuery, limit: u8) -> Result<Vec<WorkflowRun>> {
+    let repo = get_github_repo()?;
+    let client = GitHubClient::new(token)?;
+    list_runs(&client, &repo, query, limit).await
+}
+
+/// Table of workflow runs, one line per run, with colored status icons if `color` is set
+pub fn render_run_table(runs: &[WorkflowRun], color: bool) -> Vec<String> {
+    let name_width = runs.iter().map(|r| r.name.chars().count()).max().unwrap_or(0);
+    let branch_width = runs.iter().map(|r| r.head_branch.chars().count()).max().unwrap_or(0);
+    let event_width = runs.iter().map(|r| r.event.len()).max().unwrap_or(0);
+    let now = Utc::now();
+
+    runs.iter()
+        .map(|run| {
+            let status = run.status.as_deref().unwrap_or("unknown");
+            let (icon, icon_color) = status_icon(status, run.conclusion.as_deref());
+            let icon = if color {
+                icon.with(icon_color).to_string()
+            } else {
+                icon.to_string()
+            };
+            format!(
+                "{} {:<name_width$}  {:<branch_width$}  {:<event_width$}  {:<7}  {:>7}  {:>8} ago  {}",
+                icon,
+                run.name,
+                run.head_branch,
+                run.event,
+                &run.head_sha[..run.head_sha.len().min(7)],
+                format_duration(run.elapsed()),
+                format_duration(now - run.created_at),
+                run.id,
+            )
+        })
+        .collect()
+}
+
+/// Format a duration compactly, e.g. `45s`, `3m 07s`, `1h 02m` or `2d 05h`
+pub fn format_duration(duration: chrono::Duration) -> String {
+    let secs = duration.num_seconds().max(0);
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m {:02}s", secs / 60, secs % 60),
+        3600..86400 => format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60),
+        _ => format!("{}d {:02}h", secs / 86400, (secs % 86400) / 3600),
+    }
+}
+
+/// Time between `started` and `completed`, or until now if not completed yet
+pub(super) fn span(started: Option<DateTime<Utc>>, completed: Option<DateTime<Utc>>) -> Option<chrono::Duration> {
+    let started = started?;
+    Some(completed.unwrap_or_else(Utc::now) - started)
+}
+
+/// Icon and color for a job, step or check status
+pub(super) fn status_icon(status: &str, conclusion: Option<&str>) -> (&'static str, Color) {
+    match (status, conclusion) {
+        ("completed", Some("success")) => ("✓", Color::Green),
+        ("completed", Some("failure")) | ("completed", Some("timed_out")) => ("✗", Color::Red),
+        ("completed", Some("cancelled")) => ("⊘", Color::DarkGrey),
+        ("completed", Some("skipped")) | ("completed", Some("neutral")) => ("-", Color::DarkGrey),
+        ("completed", _) => ("?", Color::Yellow),
+        ("in_progress", _) => ("●", Color::Yellow),
+        _ => ("○", Color::DarkGrey),
+    }
+}
+
+/// Block of status lines that follows a watched run or check suite
+/// Redraws in place on a terminal; otherwise prints a new block only when something changes
+pub(super) struct LiveBlock {
+    tty: bool,
+    lines_drawn: u16,
+    last_snapshot: String,
+}
+
+impl LiveBlock {
+    pub(super) fn new() -> Self {
+        Self {
+            tty: io::stdout().is_terminal(),
+            lines_drawn: 0,
+            last_snapshot: String::new(),
+        }
+    }
+
+    /// Draw `lines`, unless stdout isn't a terminal and `snapshot` is unchanged since the last draw
+    pub(super) fn draw(&mut self, snapshot: String, lines: &[String]) -> io::Result<()> {
+        if !self.tty && snapshot == self.last_snapshot {
+            return Ok(());
+        }
+        self.last_snapshot = snapshot;
+
+        let mut stdout = io::stdout().lock();
+        if self.tty && self.lines_drawn > 0 {
+            execute!(stdout, MoveUp(self.lines_drawn), Clear(ClearType::FromCursorDown))?;
+        }
+        for line in lines {
+            writeln!(stdout, "{}", line)?;
+        }
+        if !self.tty {
+            writeln!(stdout)?;
+        }
+        stdout.flush()?;
+        self.lines_drawn = lines.len() as u16;
+        Ok(())
+    }
+
+    /// Status line with an icon, name and duration
+    pub(super) fn row(
+        &self,
+        indent: usize,
+        name: &str,
+        status: &str,
+        conclusion: Option<&str>,
+        duration: Option<chrono::Duration>,
+    ) -> String {
+        let (icon, color) = status_icon(status, conclusion);
+        let icon = if self.tty {
+            icon.with(color).to_string()
+        } else {
+            icon.to_string()
+        };
+        let duration = duration.map(format_duration).unwrap_or_default();
+        let width = 40usize.saturating_sub(indent);
+        format!("{:indent$}{} {:<width$} {}", "", icon, name, duration)
+    }
+
+    pub(super) fn bold(&self, text: &str) -> String {
+        if self.tty {
+            text.bold().to_string()
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Live job/step view of a workflow run
+struct RunDisplay {
+    quiet: bool,
+    block: LiveBlock,
+}
+
+impl RunDisplay {
+    fn new(quiet: bool) -> Self {
+        Self {
+            quiet,
+            block: LiveBlock::new(),
+        }
+    }
+
+    fn render(&mut self, run: &WorkflowRun, jobs: &[WorkflowJob]) -> io::Result<()> {
+        if self.quiet {
+            return Ok(());
+        }
+
+        // Status without durations, so non-tty output only repeats on real changes
+        let snapshot: String = jobs
+            .iter()
+            .map(|j| format!("{}:{}:{:?};", j.name, j.status, j.conclusion))
+            .chain(std::iter::once(format!("{:?}{:?}", run.status, run.conclusion)))
+            .collect();
+
+        let mut lines = Vec::new();
+        let status = run
+            .conclusion
+            .as_deref()
+            .or(run.status.as_deref())
+            .unwrap_or("unknown");
+        lines.push(format!(
+            "{} · {} · {} · {}",
+            self.block.bold(&run.name),
+            run.head_branch,
+            status,
+            format_duration(run.elapsed())
+        ));
+
+        for job in jobs {
+            lines.push(self.block.row(
+                2,
+                &job.name,
+                &job.status,
+                job.conclusion.as_deref(),
+                span(job.started_at, job.completed_at),
+            ));
+
+            // Expand steps for jobs that are running or failed
+            let failed = matches!(job.conclusion.as_deref(), Some("failure") | Some("timed_out"));
+            if job.status == "in_progress" || failed {
+                for step in &job.steps {
+                    lines.push(self.block.row(
+                        6,
+                        &step.name,
+                        &step.status,
+                        step.conclusion.as_deref(),
+                        span(step.started_at, step.completed_at),
+                    ));
+                }
+            }
+        }
+
+        self.block.draw(snapshot, &lines)
     }
 }
 
@@ -64,6 +324,46 @@ pub struct ActionResult {
     pub url: String,
 }
 
+impl From<&WorkflowRun> for ActionResult {
+    fn from(run: &WorkflowRun) -> Self {
+        Self {
+            workflow_name: run.name.clone(),
+            status: run.status.clone(),
+            conclusion: run.conclusion.clone(),
+            branch: run.head_branch.clone(),
+            url: run.html_url.clone(),
+        }
+    }
+}
+
+impl From<&ActionResult> for Completion {
+    fn from(result: &ActionResult) -> Self {
+        Self {
+            name: result.workflow_name.clone(),
+            branch: result.branch.clone(),
+            conclusion: result
+                .conclusion
+                .clone()
+                .or_else(|| result.status.clone())
+                .unwrap_or_else(|| "unknown".to_string()),
+            url: result.url.clone(),
+        }
+    }
+}
+
+impl ActionResult {
+    /// Process exit code for the run's conclusion
+    /// 0 success (or still running), 1 failure, 2 cancelled, 3 timed out
+    pub fn exit_code(&self) -> i32 {
+        match self.conclusion.as_deref() {
+            None | Some("success") | Some("neutral") | Some("skipped") => 0,
+            Some("cancelled") => 2,
+            Some("timed_out") => 3,
+            Some(_) => 1,
+        }
+    }
+}
+
 impl std::fmt::Display for ActionResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let status = if self.status.as_deref() == Some("in_progress")
@@ -83,3 +383,75 @@ impl std::fmt::Display for ActionResult {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(chrono::Duration::seconds(45)), "45s");
+        assert_eq!(format_duration(chrono::Duration::seconds(187)), "3m 07s");
+        assert_eq!(format_duration(chrono::Duration::seconds(3720)), "1h 02m");
+        assert_eq!(format_duration(chrono::Duration::seconds(183600)), "2d 03h");
+        assert_eq!(format_duration(chrono::Duration::seconds(-5)), "0s");
+    }
+
+    #[test]
+    fn test_render_run_table() {
+        let run = |id: u64, name: &str, branch: &str, conclusion: &str| -> WorkflowRun {
+            serde_json::from_value(serde_json::json!({
+                "id": id, "name": name, "status": "completed", "conclusion": conclusion,
+                "head_branch": branch, "head_sha": "0123456789abcdef", "event": "push",
+                "html_url": "", "created_at": "2026-01-01T10:00:00Z",
+                "updated_at": "2026-01-01T10:03:07Z", "run_started_at": "2026-01-01T10:00:00Z"
+            }))
+            .unwrap()
+        };
+
+        let lines = render_run_table(&[run(2, "CI", "feat/a+b", "failure"), run(1, "Deploy", "main", "success")], false);
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("✗ CI      feat/a+b  push  0123456   3m 07s"));
+        assert!(lines[1].starts_with("✓ Deploy  main      push  0123456   3m 07s"));
+        assert!(lines[0].ends_with(" ago  2"));
+    }
+
+    #[test]
+    fn test_exit_code_reflects_conclusion() {
+        let result = |conclusion: Option<&str>| ActionResult {
+            workflow_name: "CI".to_string(),
+            status: Some("completed".to_string()),
+            conclusion: conclusion.map(str::to_string),
+            branch: "main".to_string(),
+            url: String::new(),
+        };
+
+        assert_eq!(result(Some("success")).exit_code(), 0);
+        assert_eq!(result(Some("failure")).exit_code(), 1);
+        assert_eq!(result(Some("cancelled")).exit_code(), 2);
+        assert_eq!(result(Some("timed_out")).exit_code(), 3);
+        assert_eq!(result(None).exit_code(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_several_failures() {
+        let mut calls = 0;
+        let result = retry(Duration::ZERO, || {
+            calls += 1;
+            let ok = calls == POLL_ATTEMPTS;
+            async move { if ok { Ok(calls) } else { anyhow::bail!("connection reset") } }
+        })
+        .await;
+        assert_eq!(result.unwrap(), POLL_ATTEMPTS);
+
+        calls = 0;
+        let result: Result<()> = retry(Duration::ZERO, || {
+            calls += 1;
+            async { anyhow::bail!("connection reset") }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, POLL_ATTEMPTS);
+    }
+}
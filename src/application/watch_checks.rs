@@ -0,0 +1,375 @@
+use super::notify::Completion;
+use super::watch_action::{format_duration, span, LiveBlock};
+use crate::git::{get_current_branch, get_github_repo, get_head_sha, GitHubRepo};
+use crate::infrastructure::{CheckRun, CommitStatus, GitHubClient};
+use anyhow::Result;
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Options for `gg watch checks`
+#[derive(Debug, Clone)]
+pub struct ChecksOptions {
+    /// Time between polls
+    pub interval: Duration,
+    /// Give up once checks have been pending this long
+    pub timeout: Duration,
+}
+
+/// The commit whose checks are watched
+#[derive(Debug, Clone)]
+struct ChecksTarget {
+    /// `PR #12 Title` or `commit abc1234`
+    label: String,
+    branch: String,
+    sha: String,
+    url: String,
+}
+
+/// A check run or commit status, in check run terms
+#[derive(Debug, Clone)]
+struct Check {
+    name: String,
+    /// App that reported the check, or `status` for commit statuses
+    source: String,
+    status: String,
+    conclusion: Option<String>,
+    duration: Option<chrono::Duration>,
+}
+
+impl From<CheckRun> for Check {
+    fn from(run: CheckRun) -> Self {
+        Self {
+            duration: span(run.started_at, run.completed_at),
+            name: run.name,
+            source: run.app.map_or_else(|| "app".to_string(), |app| app.name),
+            status: run.status,
+            conclusion: run.conclusion,
+        }
+    }
+}
+
+impl From<CommitStatus> for Check {
+    fn from(status: CommitStatus) -> Self {
+        let (state, conclusion) = match status.state.as_str() {
+            "pending" => ("in_progress", None),
+            "success" => ("completed", Some("success")),
+            _ => ("completed", Some("failure")),
+        };
+        Self {
+            duration: span(
+                Some(status.created_at),
+                conclusion.map(|_| status.updated_at),
+            ),
+            name: status.context,
+            source: "status".to_string(),
+            status: state.to_string(),
+            conclusion: conclusion.map(str::to_string),
+        }
+    }
+}
+
+impl Check {
+    fn is_pending(&self) -> bool {
+        self.status != "completed"
+    }
+
+    fn is_failed(&self) -> bool {
+        !self.is_pending()
+            && !matches!(
+                self.conclusion.as_deref(),
+                Some("success") | Some("neutral") | Some("skipped")
+            )
+    }
+}
+
+/// How a check suite settled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksOutcome {
+    Success,
+    Failure,
+    /// Checks were still pending when the timeout ran out
+    TimedOut,
+}
+
+/// Result of watching the checks of a branch
+#[derive(Debug)]
+pub struct ChecksResult {
+    pub label: String,
+    pub branch: String,
+    pub url: String,
+    pub outcome: ChecksOutcome,
+    pub passed: usize,
+    pub failed: usize,
+    pub pending: usize,
+}
+
+impl ChecksResult {
+    /// Process exit code: 0 success, 1 failure, 3 still pending at the timeout
+    pub fn exit_code(&self) -> i32 {
+        match self.outcome {
+            ChecksOutcome::Success => 0,
+            ChecksOutcome::Failure => 1,
+            ChecksOutcome::TimedOut => 3,
+        }
+    }
+}
+
+impl std::fmt::Display for ChecksResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let outcome = match self.outcome {
+            ChecksOutcome::Success => "success",
+            ChecksOutcome::Failure => "failure",
+            ChecksOutcome::TimedOut => "timed out",
+        };
+        write!(
+            f,
+            "{} | {} | {} ({})",
+            self.label,
+            self.branch,
+            outcome,
+            summary(self.passed, self.failed, self.pending)
+        )
+    }
+}
+
+impl From<&ChecksResult> for Completion {
+    fn from(result: &ChecksResult) -> Self {
+        Self {
+            name: format!("Checks for {}", result.label),
+            branch: result.branch.clone(),
+            conclusion: match result.outcome {
+                ChecksOutcome::Success => "success",
+                ChecksOutcome::Failure => "failure",
+                ChecksOutcome::TimedOut => "timed_out",
+            }
+            .to_string(),
+            url: result.url.clone(),
+        }
+    }
+}
+
+/// Follow the check runs and commit statuses of the current branch's open PR (or its
+/// HEAD commit without one) until every check has settled or `timeout` runs out
+pub async fn watch_checks(token: String, options: &ChecksOptions, quiet: bool) -> Result<ChecksResult> {
+    let repo = get_github_repo()?;
+    let client = GitHubClient::new(token)?;
+    let target = find_target(&client, &repo).await?;
+    follow_checks(&client, &repo, &target, options, quiet).await
+}
+
+/// The open PR for the current branch, falling back to the local HEAD commit
+async fn find_target(client: &GitHubClient, repo: &GitHubRepo) -> Result<ChecksTarget> {
+    let branch = get_current_branch()?;
+    if let Some(pr) = client
+        .fetch_branch_pull_request(&repo.owner, &repo.name, &branch)
+        .await?
+    {
+        return Ok(ChecksTarget {
+            label: format!("PR #{} {}", pr.number, pr.title),
+            branch,
+            sha: pr.head.sha,
+            url: pr.html_url,
+        });
+    }
+
+    let sha = get_head_sha()?;
+    Ok(ChecksTarget {
+        label: format!("commit {}", &sha[..sha.len().min(7)]),
+        url: repo.url_for(&format!("commit/{}", sha)),
+        branch,
+        sha,
+    })
+}
+
+async fn follow_checks(
+    client: &GitHubClient,
+    repo: &GitHubRepo,
+    target: &ChecksTarget,
+    options: &ChecksOptions,
+    quiet: bool,
+) -> Result<ChecksResult> {
+    let started = Instant::now();
+    let mut display = ChecksDisplay::new(quiet);
+
+    loop {
+        let checks = fetch_checks(client, repo, &target.sha).await?;
+        display.render(target, &checks)?;
+
+        // No checks yet counts as pending, CI may not have picked up the commit
+        let pending = checks.is_empty() || checks.iter().any(Check::is_pending);
+        let outcome = if !pending {
+            Some(if checks.iter().any(Check::is_failed) {
+                ChecksOutcome::Failure
+            } else {
+                ChecksOutcome::Success
+            })
+        } else if started.elapsed() >= options.timeout {
+            Some(ChecksOutcome::TimedOut)
+        } else {
+            None
+        };
+
+        if let Some(outcome) = outcome {
+            let failed = checks.iter().filter(|c| c.is_failed()).count();
+            let pending = checks.iter().filter(|c| c.is_pending()).count();
+            return Ok(ChecksResult {
+                label: target.label.clone(),
+                branch: target.branch.clone(),
+                url: target.url.clone(),
+                outcome,
+                passed: checks.len() - failed - pending,
+                failed,
+                pending,
+            });
+        }
+
+        tokio::time::sleep(options.interval).await;
+    }
+}
+
+/// Check runs and commit statuses for `sha`, sorted by name
+async fn fetch_checks(client: &GitHubClient, repo: &GitHubRepo, sha: &str) -> Result<Vec<Check>> {
+    let (runs, statuses) = tokio::try_join!(
+        client.fetch_check_runs(&repo.owner, &repo.name, sha),
+        client.fetch_commit_statuses(&repo.owner, &repo.name, sha),
+    )?;
+
+    let mut checks: Vec<Check> = runs
+        .into_iter()
+        .map(Check::from)
+        .chain(statuses.into_iter().map(Check::from))
+        .collect();
+    checks.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(checks)
+}
+
+fn summary(passed: usize, failed: usize, pending: usize) -> String {
+    format!("{} passed, {} failed, {} pending", passed, failed, pending)
+}
+
+/// Live summary table of a commit's checks
+struct ChecksDisplay {
+    quiet: bool,
+    block: LiveBlock,
+}
+
+impl ChecksDisplay {
+    fn new(quiet: bool) -> Self {
+        Self {
+            quiet,
+            block: LiveBlock::new(),
+        }
+    }
+
+    fn render(&mut self, target: &ChecksTarget, checks: &[Check]) -> io::Result<()> {
+        if self.quiet {
+            return Ok(());
+        }
+
+        let snapshot: String = checks
+            .iter()
+            .map(|c| format!("{}:{}:{:?};", c.name, c.status, c.conclusion))
+            .collect();
+
+        let failed = checks.iter().filter(|c| c.is_failed()).count();
+        let pending = checks.iter().filter(|c| c.is_pending()).count();
+        let mut lines = vec![format!(
+            "{} · {} · {} · {}",
+            self.block.bold(&target.label),
+            target.branch,
+            &target.sha[..target.sha.len().min(7)],
+            summary(checks.len() - failed - pending, failed, pending)
+        )];
+        if checks.is_empty() {
+            lines.push("  Waiting for checks to be reported...".to_string());
+        }
+        let source_width = checks.iter().map(|c| c.source.chars().count()).max().unwrap_or(0);
+        for check in checks {
+            let row = self.block.row(
+                2,
+                &check.name,
+                &check.status,
+                check.conclusion.as_deref(),
+                None,
+            );
+            let duration = check.duration.map(format_duration).unwrap_or_default();
+            lines.push(format!("{} {:<source_width$}  {}", row, check.source, duration));
+        }
+
+        self.block.draw(snapshot, &lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_follow_checks_aggregates_runs_and_statuses() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/repos/octocat/hello/commits/abc123/check-runs?page=1&per_page=100")
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({"total_count": 2, "check_runs": [
+                    {"name": "build", "status": "completed", "conclusion": "success",
+                     "started_at": "2026-01-01T10:00:00Z", "completed_at": "2026-01-01T10:02:00Z",
+                     "app": {"name": "GitHub Actions"}},
+                    {"name": "lint", "status": "completed", "conclusion": "skipped",
+                     "started_at": null, "completed_at": null, "app": {"name": "GitHub Actions"}}
+                ]})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/repos/octocat/hello/commits/abc123/status?per_page=100")
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({"state": "failure", "statuses": [
+                    {"context": "ci/circleci", "state": "error",
+                     "created_at": "2026-01-01T10:00:00Z", "updated_at": "2026-01-01T10:04:00Z"}
+                ]})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let client = GitHubClient::with_base_url("token".to_string(), &server.url()).unwrap();
+        let repo = GitHubRepo {
+            owner: "octocat".to_string(),
+            name: "hello".to_string(),
+        };
+        let target = ChecksTarget {
+            label: "PR #12 Add checks".to_string(),
+            branch: "feat/checks".to_string(),
+            sha: "abc123".to_string(),
+            url: "https://github.com/octocat/hello/pull/12".to_string(),
+        };
+        let options = ChecksOptions {
+            interval: Duration::from_millis(10),
+            timeout: Duration::from_secs(5),
+        };
+
+        let result = follow_checks(&client, &repo, &target, &options, true).await.unwrap();
+
+        assert_eq!(result.outcome, ChecksOutcome::Failure);
+        assert_eq!((result.passed, result.failed, result.pending), (2, 1, 0));
+        assert_eq!(result.exit_code(), 1);
+        assert_eq!(Completion::from(&result).name, "Checks for PR #12 Add checks");
+    }
+
+    #[test]
+    fn test_pending_status_is_not_settled() {
+        let status: CommitStatus = serde_json::from_value(serde_json::json!({
+            "context": "ci/jenkins", "state": "pending",
+            "created_at": "2026-01-01T10:00:00Z", "updated_at": "2026-01-01T10:00:00Z"
+        }))
+        .unwrap();
+
+        let check = Check::from(status);
+
+        assert!(check.is_pending());
+        assert!(!check.is_failed());
+    }
+}
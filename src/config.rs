@@ -1,5 +1,13 @@
-use clap::{Parser, Subcommand};
+use crate::git::get_repo_root;
+use crate::infrastructure::load_workflows;
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap_complete::engine::{ArgValueCompleter, CompletionCandidate};
+use clap_complete::CompleteEnv;
+use std::ffi::OsStr;
 use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
 
 /// g - A personalized GitHub CLI tool
 #[derive(Parser, Debug)]
@@ -11,10 +19,26 @@ pub struct Cli {
     #[arg(global = true, long, env = "GITHUB_TOKEN")]
     pub token: Option<String>,
 
+    /// Path to the cache database (defaults to the XDG cache directory)
+    #[arg(global = true, long, env = "GG_CACHE_PATH")]
+    pub cache_path: Option<PathBuf>,
+
+    /// Path to the settings file (defaults to config.yaml in the XDG config directory)
+    #[arg(global = true, long, env = "GG_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Refresh the cache in the background when the TUI starts with data older than this many hours (0 disables)
+    #[arg(global = true, long, env = "GG_REFRESH_TTL", default_value_t = 24)]
+    pub refresh_ttl: u64,
+
     /// Suppress progress indicators and non-error output
     #[arg(global = true, long, short)]
     pub quiet: bool,
 
+    /// TUI options, for running `gg` without a sub-command (rejected alongside one)
+    #[command(flatten)]
+    pub tui: TuiArgs,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -22,7 +46,7 @@ pub struct Cli {
 #[derive(Subcommand, Debug, Clone)]
 pub enum Commands {
     /// Launch the TUI fuzzy finder explicitly
-    Tui,
+    Tui(TuiArgs),
 
     /// Data management commands
     Data {
@@ -33,8 +57,11 @@ pub enum Commands {
     /// Open the current repo's Issues page
     Issues,
 
-    /// Open the current repo's Actions page
-    Actions,
+    /// Open the current repo's Actions page, or work with its workflow runs
+    Actions {
+        #[command(subcommand)]
+        action: Option<ActionsCommands>,
+    },
 
     /// Open the current repo's Settings page
     Settings,
@@ -76,23 +103,240 @@ pub enum DataCommands {
     Clear,
     /// Show cache statistics
     Status,
-    /// Export cached data as JSON to stdout
-    Export,
+    /// Export cached data (including usage) to stdout
+    Export {
+        /// Output format
+        #[arg(long, short, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+        /// Only export repos owned by this org or user
+        #[arg(long)]
+        org: Option<String>,
+        /// Only export repos with this primary language
+        #[arg(long)]
+        language: Option<String>,
+        /// Only export private repos
+        #[arg(long)]
+        private: bool,
+        /// Only export repos fuzzy matching this query (same matching as the TUI)
+        #[arg(long)]
+        query: Option<String>,
+    },
+    /// Import data previously written by `gg data export`
+    Import {
+        /// Path to the export file (use - for stdin)
+        file: PathBuf,
+        /// How to combine the imported data with the existing cache
+        #[arg(long, value_enum, default_value_t = ImportMode::Merge)]
+        mode: ImportMode,
+    },
     /// Show the database file path
     Reveal,
+    /// Compact the database and checkpoint its write-ahead log
+    Vacuum,
+    /// Check the database for corruption and offer to repair it
+    Check {
+        /// Repair without asking if problems are found
+        #[arg(long)]
+        repair: bool,
+    },
+    /// Remove repos not seen by a refresh recently and reset old usage
+    Prune {
+        /// Age cutoff, e.g. 12h, 30d or 8w
+        #[arg(long, default_value = "30d", value_parser = parse_age)]
+        older_than: Duration,
+    },
+}
+
+/// Options for the TUI fuzzy finder
+#[derive(Args, Debug, Clone)]
+pub struct TuiArgs {
+    /// Print the selection instead of opening it: its full_name, or a template like '{owner}/{name}' or 'url'
+    #[arg(long, num_args = 0..=1, default_missing_value = "full_name", value_name = "TEMPLATE")]
+    pub print: Option<String>,
+    /// Start with this query
+    #[arg(long)]
+    pub query: Option<String>,
+    /// Accept the only match right away without showing the TUI
+    #[arg(long = "select-1")]
+    pub select_one: bool,
+    /// Exit right away when nothing matches
+    #[arg(long = "exit-0")]
+    pub exit_zero: bool,
+}
+
+impl TuiArgs {
+    /// Whether any TUI option was given
+    fn is_set(&self) -> bool {
+        self.print.is_some() || self.query.is_some() || self.select_one || self.exit_zero
+    }
+}
+
+/// How to tell you a watched run has finished
+#[derive(Args, Debug, Clone)]
+pub struct NotifyArgs {
+    /// Ring the terminal bell when the run finishes
+    #[arg(long)]
+    pub bell: bool,
+    /// Show a desktop notification through the terminal (OSC 9, or OSC 777 for terminals like foot and rxvt)
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "osc9", value_name = "PROTOCOL")]
+    pub notify: Option<TerminalNotification>,
+    /// Run a shell command when the run finishes; {name}, {branch}, {conclusion} and {url} are replaced
+    #[arg(long, value_name = "COMMAND")]
+    pub on_complete: Option<String>,
+    /// POST the result as JSON to this URL when the run finishes
+    #[arg(long, value_name = "URL")]
+    pub webhook: Option<String>,
+}
+
+/// Which workflow run(s) to act on
+#[derive(Args, Debug, Clone)]
+pub struct RunArgs {
+    /// Workflow run id (defaults to the running or latest matching run for the current branch)
+    pub run: Option<u64>,
+    /// Only runs of this workflow file (e.g. ci.yml) or workflow name
+    #[arg(long, short)]
+    pub workflow: Option<String>,
+    /// Only runs for this branch (defaults to the current branch)
+    #[arg(long)]
+    pub branch: Option<String>,
+    /// Only runs triggered by this event, e.g. push or pull_request
+    #[arg(long)]
+    pub event: Option<String>,
+    /// Only runs for this commit (defaults to the local HEAD if it has any runs)
+    #[arg(long = "sha")]
+    pub head_sha: Option<String>,
+    /// Only runs with this status or conclusion
+    #[arg(long, value_parser = [
+        "queued", "in_progress", "completed", "waiting", "requested", "pending",
+        "success", "failure", "cancelled", "skipped", "timed_out", "action_required",
+        "neutral", "stale",
+    ])]
+    pub status: Option<String>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ActionsCommands {
+    /// Dashboard of the latest workflow run in each of your most used repos
+    Dashboard {
+        /// Number of repos to show, picked by how often and recently you open them
+        #[arg(long, short = 'n', default_value_t = 15)]
+        count: usize,
+        /// Seconds between refreshes
+        #[arg(long, default_value_t = 30)]
+        interval: u64,
+    },
+    /// Success rate, durations, queue times and flaky jobs of recent workflow runs
+    Stats {
+        /// Only runs of this workflow file (e.g. ci.yml) or workflow name
+        #[arg(long, short, add = ArgValueCompleter::new(complete_workflow))]
+        workflow: Option<String>,
+        /// Number of days of history to include
+        #[arg(long, default_value_t = 30)]
+        days: u32,
+        /// Fetch new runs even if the cached history is recent
+        #[arg(long)]
+        refresh: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the log output of failed steps in a workflow run
+    Logs {
+        #[command(flatten)]
+        run: RunArgs,
+        /// Print the whole log of each job instead of only the failing steps
+        #[arg(long)]
+        full: bool,
+        /// Only show jobs whose name contains this text, even if they passed
+        #[arg(long)]
+        job: Option<String>,
+    },
+    /// List the artifacts uploaded by a workflow run
+    Artifacts {
+        #[command(flatten)]
+        run: RunArgs,
+    },
+    /// Download and unzip the artifacts of a workflow run
+    Download {
+        /// Artifact to download (defaults to every artifact, each in its own directory)
+        name: Option<String>,
+        /// Directory to extract into
+        #[arg(long, short = 'o', default_value = ".")]
+        dir: PathBuf,
+        #[command(flatten)]
+        run: RunArgs,
+    },
+    /// Re-run a completed workflow run
+    Rerun {
+        #[command(flatten)]
+        run: RunArgs,
+        /// Only re-run the failed jobs
+        #[arg(long)]
+        failed: bool,
+    },
+    /// Cancel a queued or in-progress workflow run
+    Cancel {
+        #[command(flatten)]
+        run: RunArgs,
+    },
+    /// Trigger a workflow that has a workflow_dispatch trigger
+    Dispatch {
+        /// Workflow file (e.g. deploy.yml) or name
+        #[arg(add = ArgValueCompleter::new(complete_workflow))]
+        workflow: String,
+        /// Branch or tag to run the workflow on (defaults to the current branch)
+        #[arg(long = "ref")]
+        git_ref: Option<String>,
+        /// Workflow input as key=value (repeatable)
+        #[arg(short = 'f', long = "field", value_parser = parse_field, add = ArgValueCompleter::new(complete_field))]
+        fields: Vec<(String, String)>,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum WatchCommands {
-    /// Show running or most recent action for current repo/branch
-    Action,
+    /// Follow the running or most recent action for current repo/branch until it completes
+    ///
+    /// Exits 0 on success, 1 on failure, 2 if cancelled, 3 if timed out and 4 on errors
+    Action {
+        #[command(flatten)]
+        run: RunArgs,
+        /// Open the run in the browser instead of following it in the terminal
+        #[arg(long)]
+        open: bool,
+        /// Print a table of recent matching runs instead of following one
+        #[arg(long, conflicts_with = "open")]
+        list: bool,
+        /// Number of runs to show with --list (at most 100)
+        #[arg(long, default_value_t = 20, requires = "list", value_parser = clap::value_parser!(u8).range(1..=100))]
+        limit: u8,
+        /// Seconds between status polls
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+        #[command(flatten)]
+        notify: NotifyArgs,
+    },
+    /// Follow the checks of the current branch's pull request (or HEAD commit) until they settle
+    ///
+    /// Includes check runs from any app and commit statuses from third-party CI.
+    /// Exits 0 if every check passed, 1 if any failed and 3 if checks were still pending at the timeout
+    Checks {
+        /// Seconds between status polls
+        #[arg(long, default_value_t = 10)]
+        interval: u64,
+        /// Minutes to wait for pending checks before giving up
+        #[arg(long, default_value_t = 60)]
+        timeout: u64,
+        #[command(flatten)]
+        notify: NotifyArgs,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum RaycastCommands {
     /// Search repos using fuzzy matching
     Search {
-        /// Search query
+        /// Search query, fuzzy text plus qualifiers like `org:revsys` or `-is:fork`
         query: String,
         /// Maximum number of results to return
         #[arg(short, long, default_value = "10")]
@@ -103,6 +347,34 @@ pub enum RaycastCommands {
     },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Versioned JSON document that `gg data import` can read
+    Json,
+    /// One JSON repo object per line
+    Ndjson,
+    /// Comma separated values with a header row
+    Csv,
+    /// One full_name per line
+    Names,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImportMode {
+    /// Add imported items, keeping the highest usage of local and imported data
+    Merge,
+    /// Replace the cached orgs and repos with the imported ones
+    Replace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TerminalNotification {
+    /// `ESC ] 9 ; message` (iTerm2, Windows Terminal, WezTerm, Ghostty, kitty)
+    Osc9,
+    /// `ESC ] 777 ; notify ; title ; body` (foot, urxvt, VTE based terminals)
+    Osc777,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub enum ViewType {
     Repo,
@@ -126,14 +398,158 @@ impl fmt::Display for ViewType {
     }
 }
 
+/// Parse an age like `12h`, `30d` or `8w` into a duration
+pub fn parse_age(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid age '{}', expected e.g. 12h, 30d or 8w", value))?;
+    let hours_per_unit = match unit {
+        "h" => 1,
+        "d" | "" => 24,
+        "w" => 24 * 7,
+        _ => return Err(format!("unknown unit '{}', expected h, d or w", unit)),
+    };
+
+    amount
+        .checked_mul(hours_per_unit * 60 * 60)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("age '{}' is too large", value))
+}
+
+/// Parse a `key=value` workflow input
+pub fn parse_field(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("invalid input '{}', expected key=value", value)),
+    }
+}
+
+/// Complete dispatchable workflow file names from the local `.github/workflows`
+fn complete_workflow(current: &OsStr) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    let Ok(root) = get_repo_root() else {
+        return Vec::new();
+    };
+    load_workflows(&root)
+        .into_iter()
+        .filter(|workflow| workflow.dispatch_inputs.is_some())
+        .filter(|workflow| workflow.file_name.starts_with(current.as_ref()))
+        .map(|workflow| {
+            CompletionCandidate::new(&workflow.file_name).help(workflow.name.map(Into::into))
+        })
+        .collect()
+}
+
+/// Complete `key=` for dispatch inputs and, after the `=`, the values of boolean and choice inputs
+fn complete_field(current: &OsStr) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    let Ok(root) = get_repo_root() else {
+        return Vec::new();
+    };
+    let mut seen = std::collections::HashSet::new();
+    let inputs = load_workflows(&root)
+        .into_iter()
+        .flat_map(|workflow| workflow.dispatch_inputs.unwrap_or_default());
+
+    match current.split_once('=') {
+        Some((key, partial)) => inputs
+            .filter(|input| input.name == key)
+            .flat_map(|input| match input.input_type.as_str() {
+                "boolean" => vec!["true".to_string(), "false".to_string()],
+                _ => input.options,
+            })
+            .filter(|value| value.starts_with(partial) && seen.insert(value.clone()))
+            .map(|value| CompletionCandidate::new(format!("{}={}", key, value)))
+            .collect(),
+        None => inputs
+            .filter(|input| input.name.starts_with(current.as_ref()) && seen.insert(input.name.clone()))
+            .map(|input| {
+                CompletionCandidate::new(format!("{}=", input.name))
+                    .help(input.description.map(Into::into))
+            })
+            .collect(),
+    }
+}
+
 pub fn parse_args() -> Cli {
-    Cli::parse()
+    // Answer dynamic completion requests (`COMPLETE=bash gg`) before normal parsing
+    CompleteEnv::with_factory(Cli::command).bin("gg").complete();
+    try_parse_cli(std::env::args_os()).unwrap_or_else(|e| e.exit())
+}
+
+/// Parse the command line, rejecting top-level TUI options given with a sub-command
+/// Global options like `-q` and `--token` still go anywhere
+fn try_parse_cli<I, T>(args: I) -> Result<Cli, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let cli = Cli::try_parse_from(args)?;
+    if cli.command.is_some() && cli.tui.is_set() {
+        return Err(Cli::command().error(
+            ErrorKind::ArgumentConflict,
+            "--print, --query, --select-1 and --exit-0 only apply to the TUI, use them without a sub-command or after `tui`",
+        ));
+    }
+    Ok(cli)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("12h"), Ok(Duration::from_secs(12 * 3600)));
+        assert_eq!(parse_age("30d"), Ok(Duration::from_secs(30 * 86400)));
+        assert_eq!(parse_age("2w"), Ok(Duration::from_secs(14 * 86400)));
+        assert_eq!(parse_age("7"), Ok(Duration::from_secs(7 * 86400)));
+        assert!(parse_age("3y").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("99999999999999999w").is_err());
+    }
+
+    #[test]
+    fn test_parse_field() {
+        assert_eq!(
+            parse_field("environment=staging"),
+            Ok(("environment".to_string(), "staging".to_string()))
+        );
+        assert_eq!(parse_field("tag=a=b"), Ok(("tag".to_string(), "a=b".to_string())));
+        assert_eq!(parse_field("empty="), Ok(("empty".to_string(), String::new())));
+        assert!(parse_field("novalue").is_err());
+        assert!(parse_field("=x").is_err());
+    }
+
+    #[test]
+    fn test_global_options_before_sub_commands() {
+        let cli = Cli::try_parse_from(["gg", "-q", "data", "status"]).unwrap();
+        assert!(cli.quiet);
+        assert!(try_parse_cli(["gg", "--token", "X", "--cache-path", "c.db", "data", "status"]).is_ok());
+
+        let cli = try_parse_cli(["gg", "--print", "url", "--select-1"]).unwrap();
+        assert_eq!(cli.tui.print.as_deref(), Some("url"));
+        assert!(cli.command.is_none());
+        assert!(try_parse_cli(["gg", "tui", "--query", "api"]).is_ok());
+        let err = try_parse_cli(["gg", "--query", "api", "data", "status"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn test_list_limit_range() {
+        assert!(Cli::try_parse_from(["gg", "watch", "action", "--list", "--limit", "100"]).is_ok());
+        for limit in ["0", "200"] {
+            let err = Cli::try_parse_from(["gg", "watch", "action", "--list", "--limit", limit]).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        }
+    }
+
     #[test]
     fn test_view_type_display() {
         assert_eq!(ViewType::Issues.to_string(), "issues");
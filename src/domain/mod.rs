@@ -1,3 +1,5 @@
+pub mod query;
 pub mod repo;
 
+pub use query::RepoQuery;
 pub use repo::{Org, Repo};
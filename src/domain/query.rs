@@ -0,0 +1,175 @@
+use super::Repo;
+
+/// A search query split into fuzzy text and qualifiers such as `org:revsys`,
+/// `lang:rust`, `is:private` or `-org:foo`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepoQuery {
+    /// Everything that isn't a qualifier, for fuzzy matching
+    pub text: String,
+    filters: Vec<Filter>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Filter {
+    negated: bool,
+    kind: FilterKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FilterKind {
+    Org(String),
+    Lang(String),
+    Private,
+    Public,
+    IsOrg,
+    Fork,
+}
+
+impl FilterKind {
+    /// Parse `key:value`, or None when it isn't a known qualifier
+    fn parse(key: &str, value: &str) -> Option<Self> {
+        match (key.to_ascii_lowercase().as_str(), value.to_ascii_lowercase().as_str()) {
+            ("org", _) => Some(Self::Org(value.to_string())),
+            ("lang", _) => Some(Self::Lang(value.to_string())),
+            ("is", "private") => Some(Self::Private),
+            ("is", "public") => Some(Self::Public),
+            ("is", "org") => Some(Self::IsOrg),
+            ("is", "fork") => Some(Self::Fork),
+            _ => None,
+        }
+    }
+
+    /// Qualifier name, positive filters sharing one are alternatives
+    fn key(&self) -> &'static str {
+        match self {
+            Self::Org(_) => "org",
+            Self::Lang(_) => "lang",
+            _ => "is",
+        }
+    }
+
+    fn matches(&self, repo: &Repo, is_org: bool) -> bool {
+        match self {
+            Self::Org(org) => repo.owner_login.eq_ignore_ascii_case(org),
+            Self::Lang(lang) => repo
+                .language
+                .as_ref()
+                .is_some_and(|l| l.eq_ignore_ascii_case(lang)),
+            Self::Private => !is_org && repo.private,
+            Self::Public => !is_org && !repo.private,
+            Self::IsOrg => is_org,
+            Self::Fork => !is_org && repo.fork,
+        }
+    }
+}
+
+impl RepoQuery {
+    /// Split `input` on whitespace, pulling out known qualifiers
+    /// Unknown qualifiers stay in the fuzzy text, and a qualifier without a value yet
+    /// (`org:` while typing) is ignored
+    pub fn parse(input: &str) -> Self {
+        let mut words = Vec::new();
+        let mut filters = Vec::new();
+
+        for word in input.split_whitespace() {
+            let (negated, qualifier) = match word.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, word),
+            };
+            let Some((key, value)) = qualifier.split_once(':') else {
+                words.push(word);
+                continue;
+            };
+            if value.is_empty() && matches!(key.to_ascii_lowercase().as_str(), "org" | "lang" | "is") {
+                continue;
+            }
+            match FilterKind::parse(key, value) {
+                Some(kind) => filters.push(Filter { negated, kind }),
+                None => words.push(word),
+            }
+        }
+
+        Self {
+            text: words.join(" "),
+            filters,
+        }
+    }
+
+    /// Whether both queries have the same qualifiers
+    pub fn same_filters(&self, other: &RepoQuery) -> bool {
+        self.filters == other.filters
+    }
+
+    /// Whether `repo` passes the qualifiers, `is_org` marking `org/` pseudo-items
+    /// Qualifiers with the same name are alternatives (`org:a org:b` matches either),
+    /// different ones must all hold and negated ones must all fail
+    pub fn matches(&self, repo: &Repo, is_org: bool) -> bool {
+        self.filters.iter().all(|filter| {
+            if filter.negated {
+                return !filter.kind.matches(repo, is_org);
+            }
+            self.filters
+                .iter()
+                .filter(|other| !other.negated && other.kind.key() == filter.kind.key())
+                .any(|other| other.kind.matches(repo, is_org))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(full_name: &str, language: Option<&str>, private: bool, fork: bool) -> Repo {
+        let (owner, name) = full_name.split_once('/').unwrap();
+        let mut repo = Repo::new(
+            1,
+            name.to_string(),
+            full_name.to_string(),
+            1,
+            owner.to_string(),
+            private,
+            None,
+            language.map(str::to_string),
+            None,
+        );
+        repo.fork = fork;
+        repo
+    }
+
+    #[test]
+    fn test_parse_splits_text_and_qualifiers() {
+        let query = RepoQuery::parse("api org:revsys  url:x -is:fork org: gateway");
+
+        assert_eq!(query.text, "api url:x gateway");
+        assert_eq!(query.filters.len(), 2);
+        assert!(query.filters[1].negated);
+        assert_eq!(RepoQuery::parse("  api "), RepoQuery { text: "api".to_string(), filters: Vec::new() });
+    }
+
+    #[test]
+    fn test_matches() {
+        let rust = repo("revsys/gg", Some("Rust"), true, false);
+        let fork = repo("revsys/django", Some("Python"), false, true);
+        let other = repo("other/site", None, false, false);
+        let org = repo("revsys/", None, false, false);
+
+        let query = RepoQuery::parse("org:REVSYS lang:rust");
+        assert!(query.matches(&rust, false));
+        assert!(!query.matches(&fork, false));
+
+        let query = RepoQuery::parse("org:revsys org:other -is:fork");
+        assert!(query.matches(&rust, false));
+        assert!(query.matches(&other, false));
+        assert!(!query.matches(&fork, false));
+
+        let query = RepoQuery::parse("is:public");
+        assert!(query.matches(&fork, false));
+        assert!(!query.matches(&rust, false));
+        assert!(!query.matches(&org, true));
+
+        assert!(RepoQuery::parse("is:org").matches(&org, true));
+        assert!(RepoQuery::parse("-org:revsys").matches(&other, false));
+        assert!(!RepoQuery::parse("-org:revsys").matches(&org, true));
+    }
+}
@@ -44,12 +44,15 @@ pub struct Repo {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_branch: Option<String>,
     #[serde(default)]
+    pub fork: bool,
+    #[serde(default)]
     pub last_accessed_at: Option<DateTime<Utc>>,
     #[serde(default)]
     pub access_count: u32,
 }
 
 impl Repo {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: i64,
         name: String,
@@ -71,6 +74,7 @@ impl Repo {
             description,
             language,
             default_branch,
+            fork: false,
             last_accessed_at: None,
             access_count: 0,
         }
@@ -1,5 +1,6 @@
 use anyhow::{anyhow, Context, Result};
 use git2::Repository;
+use std::path::{Path, PathBuf};
 
 /// Represents a GitHub repository parsed from git config
 #[derive(Debug, Clone)]
@@ -67,12 +68,20 @@ pub fn get_github_repo() -> Result<GitHubRepo> {
 
     let remote_url = remote
         .url()
-        .ok_or_else(|| GitRepoError::NoRemoteFound)?;
+        .ok_or(GitRepoError::NoRemoteFound)?;
 
     // Parse the URL to extract owner and repo name
     parse_github_url(remote_url)
 }
 
+/// Get the root directory of the current git repository's working tree
+pub fn get_repo_root() -> Result<PathBuf> {
+    let repo = find_git_repo()?;
+    repo.workdir()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| anyhow!("Git repository has no working directory"))
+}
+
 /// Get the current branch name of the git repository
 pub fn get_current_branch() -> Result<String> {
     let repo = find_git_repo()?;
@@ -83,6 +92,16 @@ pub fn get_current_branch() -> Result<String> {
     Ok(branch_name.to_string())
 }
 
+/// Get the commit sha that HEAD points to
+pub fn get_head_sha() -> Result<String> {
+    let repo = find_git_repo()?;
+    let commit = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .context("Failed to resolve HEAD to a commit")?;
+    Ok(commit.id().to_string())
+}
+
 /// Parse a GitHub remote URL (SSH or HTTPS) into owner and repo name
 fn parse_github_url(url: &str) -> Result<GitHubRepo> {
     // Handle SSH URLs: git@github.com:owner/repo.git
@@ -1,31 +1,97 @@
+use super::github_api::{WorkflowJob, WorkflowRun};
 use crate::domain::{Org, Repo};
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, OptionalExtension};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-/// Cache file location following XDG base directory specification
-pub fn cache_path() -> Result<PathBuf> {
-    let base_dir = dirs::config_dir().context("Could not determine config directory")?;
+/// Resolve the cache file location
+/// An explicit path (from `--cache-path` or `GG_CACHE_PATH`) wins over the default
+pub fn cache_path(override_path: Option<&Path>) -> Result<PathBuf> {
+    let path = match override_path {
+        Some(path) => path.to_path_buf(),
+        None => default_cache_path()?,
+    };
 
-    let cache_dir = base_dir.join("g");
-    std::fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).context("Failed to create cache directory")?;
+    }
+
+    Ok(path)
+}
+
+/// Default cache file location following the XDG base directory specification
+/// Migrates a cache left in the old config directory location on first use
+fn default_cache_path() -> Result<PathBuf> {
+    let base_dir = dirs::cache_dir().context("Could not determine cache directory")?;
+    let path = base_dir.join("gg").join("cache.db");
+
+    if let Some(legacy) = dirs::config_dir().map(|d| d.join("g").join("cache.db")) {
+        migrate_legacy_cache(&legacy, &path)?;
+    }
+
+    Ok(path)
+}
+
+/// Move a cache database (and its WAL/SHM files) from `from` to `to`
+/// Does nothing if there is nothing to migrate or `to` already exists
+fn migrate_legacy_cache(from: &Path, to: &Path) -> Result<()> {
+    if to.exists() || !from.exists() {
+        return Ok(());
+    }
+
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent).context("Failed to create cache directory")?;
+    }
 
-    Ok(cache_dir.join("cache.db"))
+    for suffix in ["", "-wal", "-shm"] {
+        let src = PathBuf::from(format!("{}{}", from.display(), suffix));
+        let dst = PathBuf::from(format!("{}{}", to.display(), suffix));
+        if !src.exists() {
+            continue;
+        }
+        // Rename fails across filesystems, so fall back to copy + remove
+        if std::fs::rename(&src, &dst).is_err() {
+            std::fs::copy(&src, &dst)
+                .with_context(|| format!("Failed to migrate cache from {}", src.display()))?;
+            std::fs::remove_file(&src)?;
+        }
+    }
+
+    Ok(())
 }
 
+/// Current version of the cache schema, stored in `PRAGMA user_version`
+pub const SCHEMA_VERSION: i64 = 4;
+
 /// SQLite cache for storing GitHub data
 pub struct Cache {
     conn: Connection,
+    /// Database file location (None for in-memory caches)
+    path: Option<PathBuf>,
 }
 
 impl Cache {
-    /// Open or create the cache database
-    pub fn open() -> Result<Self> {
-        let path = cache_path()?;
-        let conn = Connection::open(&path)
+    /// Open or create the cache database at the given path
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
             .with_context(|| format!("Failed to open cache at {:?}", path))?;
 
-        let cache = Self { conn };
+        let cache = Self {
+            conn,
+            path: Some(path.to_path_buf()),
+        };
+        cache.init_schema()?;
+        Ok(cache)
+    }
+
+    /// Create an empty cache that lives only in memory
+    #[cfg(test)]
+    pub fn in_memory() -> Result<Self> {
+        let conn = Connection::open_in_memory().context("Failed to open in-memory cache")?;
+
+        let cache = Self { conn, path: None };
         cache.init_schema()?;
         Ok(cache)
     }
@@ -85,14 +151,187 @@ impl Cache {
         exec("CREATE INDEX IF NOT EXISTS idx_repos_owner ON repos(owner_id)")?;
         exec("CREATE INDEX IF NOT EXISTS idx_orgs_login ON orgs(login)")?;
 
+        self.migrate()
+    }
+
+    /// Bring an existing database up to `SCHEMA_VERSION`
+    fn migrate(&self) -> Result<()> {
+        let version = self.schema_version()?;
+        if version > SCHEMA_VERSION {
+            anyhow::bail!(
+                "Cache schema version {} is newer than supported version {}. Please upgrade gg.",
+                version,
+                SCHEMA_VERSION
+            );
+        }
+
+        if version < 2 {
+            // Track when each repo was last returned by the API so stale repos can be pruned
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute("ALTER TABLE repos ADD COLUMN last_seen_at TEXT", [])?;
+            tx.execute(
+                "UPDATE repos SET last_seen_at = ?1",
+                params![Utc::now().to_rfc3339()],
+            )?;
+            tx.pragma_update(None, "user_version", 2)?;
+            tx.commit()?;
+        }
+
+        if version < 3 {
+            // Completed workflow runs and their jobs, so `gg actions stats` doesn't refetch them
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS workflow_runs (
+                    id INTEGER PRIMARY KEY,
+                    repo TEXT NOT NULL,
+                    name TEXT NOT NULL,
+                    path TEXT,
+                    head_branch TEXT NOT NULL,
+                    head_sha TEXT NOT NULL,
+                    event TEXT NOT NULL,
+                    conclusion TEXT,
+                    html_url TEXT NOT NULL,
+                    run_attempt INTEGER NOT NULL,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL,
+                    run_started_at TEXT
+                )",
+                [],
+            )?;
+            tx.execute(
+                "CREATE INDEX IF NOT EXISTS idx_workflow_runs_repo ON workflow_runs(repo, created_at)",
+                [],
+            )?;
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS workflow_jobs (
+                    id INTEGER PRIMARY KEY,
+                    run_id INTEGER NOT NULL,
+                    run_attempt INTEGER NOT NULL,
+                    name TEXT NOT NULL,
+                    conclusion TEXT,
+                    created_at TEXT,
+                    started_at TEXT,
+                    completed_at TEXT
+                )",
+                [],
+            )?;
+            tx.execute(
+                "CREATE INDEX IF NOT EXISTS idx_workflow_jobs_run_id ON workflow_jobs(run_id)",
+                [],
+            )?;
+            tx.execute(
+                "CREATE TABLE IF NOT EXISTS run_history_sync (
+                    repo TEXT PRIMARY KEY,
+                    since TEXT NOT NULL,
+                    synced_at TEXT NOT NULL
+                )",
+                [],
+            )?;
+            tx.pragma_update(None, "user_version", 3)?;
+            tx.commit()?;
+        }
+
+        if version < 4 {
+            // Forks are filtered with `is:fork`, existing rows pick it up on the next refresh
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute("ALTER TABLE repos ADD COLUMN fork BOOLEAN NOT NULL DEFAULT 0", [])?;
+            tx.pragma_update(None, "user_version", 4)?;
+            tx.commit()?;
+        }
+
+        Ok(())
+    }
+
+    /// Schema version stored in the database (`PRAGMA user_version`)
+    pub fn schema_version(&self) -> Result<i64> {
+        Ok(self
+            .conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))?)
+    }
+
+    /// Run `PRAGMA integrity_check`, returning the problems found (empty when healthy)
+    pub fn integrity_check(&self) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+        let messages = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(messages.into_iter().filter(|m| m != "ok").collect())
+    }
+
+    /// Rebuild all indexes, which repairs the most common kind of corruption
+    pub fn reindex(&self) -> Result<()> {
+        self.conn.execute_batch("REINDEX")?;
         Ok(())
     }
 
+    /// Checkpoint the WAL into the main database file and reclaim free pages
+    pub fn vacuum(&self) -> Result<()> {
+        self.conn
+            .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        self.conn.execute_batch("VACUUM")?;
+        Ok(())
+    }
+
+    /// Remove repos not seen by a refresh since `cutoff`, reset usage older than it and drop
+    /// older run history. Repos are only removed when a refresh has run since `cutoff`, as
+    /// otherwise every repo would look unseen.
+    pub fn prune(&self, cutoff: DateTime<Utc>) -> Result<PruneStats> {
+        let refreshed_since_cutoff = self.last_refresh()?.is_some_and(|at| at >= cutoff);
+        let tx = self.conn.unchecked_transaction()?;
+
+        let is_stale = |timestamp: Option<String>| parse_timestamp(timestamp).is_some_and(|t| t < cutoff);
+        let stale_ids = |column: &str| -> Result<Vec<i64>> {
+            let mut stmt = tx.prepare(&format!("SELECT id, {} FROM repos", column))?;
+            Ok(stmt
+                .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .filter_map(|(id, timestamp)| is_stale(timestamp).then_some(id))
+                .collect())
+        };
+
+        let stale_repos = if refreshed_since_cutoff { stale_ids("last_seen_at")? } else { Vec::new() };
+        for id in &stale_repos {
+            tx.execute("DELETE FROM repos WHERE id = ?1", params![id])?;
+        }
+
+        // Old usage no longer says much about which repos matter, so it stops boosting them
+        let stale_usage = stale_ids("last_accessed_at")?;
+        for id in &stale_usage {
+            tx.execute(
+                "UPDATE repos SET access_count = 0, last_accessed_at = NULL WHERE id = ?1",
+                params![id],
+            )?;
+        }
+
+        // Run history is written by us in RFC 3339, so it can be compared as text
+        let cutoff = cutoff.to_rfc3339();
+        tx.execute(
+            "DELETE FROM workflow_jobs WHERE run_id IN (SELECT id FROM workflow_runs WHERE created_at < ?1)",
+            params![cutoff],
+        )?;
+        let runs_removed = tx.execute("DELETE FROM workflow_runs WHERE created_at < ?1", params![cutoff])?;
+        // Syncs reaching back before the cutoff no longer cover their window
+        tx.execute("DELETE FROM run_history_sync WHERE since < ?1", params![cutoff])?;
+
+        tx.commit()?;
+        Ok(PruneStats {
+            repos_checked: refreshed_since_cutoff,
+            repos_removed: stale_repos.len(),
+            usage_reset: stale_usage.len(),
+            runs_removed,
+        })
+    }
+
     /// Clear all data from the cache
     pub fn clear(&self) -> Result<()> {
         self.conn.execute("DELETE FROM repos", [])?;
         self.conn.execute("DELETE FROM orgs", [])?;
         self.conn.execute("DELETE FROM metadata", [])?;
+        self.conn.execute("DELETE FROM workflow_jobs", [])?;
+        self.conn.execute("DELETE FROM workflow_runs", [])?;
+        self.conn.execute("DELETE FROM run_history_sync", [])?;
         Ok(())
     }
 
@@ -106,41 +345,125 @@ impl Cache {
             .conn
             .query_row("SELECT COUNT(*) FROM repos", [], |row| row.get(0))?;
 
-        let size_bytes = std::fs::metadata(cache_path()?)?.len();
+        let size_bytes = match &self.path {
+            Some(path) => std::fs::metadata(path)?.len(),
+            None => self.conn.query_row(
+                "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+                [],
+                |row| row.get::<_, i64>(0),
+            )? as u64,
+        };
+
+        let private_count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM repos WHERE private != 0",
+            [],
+            |row| row.get(0),
+        )?;
+
+        // last_seen_at tells us when a refresh last returned each org's repos
+        let orgs = self
+            .conn
+            .prepare(
+                "SELECT owner_login, COUNT(*), MAX(last_seen_at) FROM repos
+                 GROUP BY owner_login ORDER BY COUNT(*) DESC, owner_login",
+            )?
+            .query_map([], |row| {
+                Ok(OrgStats {
+                    login: row.get(0)?,
+                    repo_count: row.get(1)?,
+                    last_refresh_at: parse_timestamp(row.get(2)?),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let languages = self
+            .conn
+            .prepare(
+                "SELECT COALESCE(language, 'Unknown'), COUNT(*) FROM repos
+                 GROUP BY 1 ORDER BY COUNT(*) DESC, 1",
+            )?
+            .query_map([], |row| {
+                Ok(LanguageStats {
+                    language: row.get(0)?,
+                    repo_count: row.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Rank by the same frecency score the TUI uses
+        let mut used: Vec<Repo> = self
+            .load_repos()?
+            .into_iter()
+            .filter(|r| r.access_count > 0)
+            .collect();
+        used.sort_by(|a, b| b.score().total_cmp(&a.score()));
+        let top_repos = used
+            .into_iter()
+            .take(TOP_REPOS_COUNT)
+            .map(|r| RepoUsageStats {
+                score: r.score(),
+                full_name: r.full_name,
+                access_count: r.access_count,
+                last_accessed_at: r.last_accessed_at,
+            })
+            .collect();
 
         Ok(CacheStats {
             org_count,
             repo_count,
             size_bytes,
+            schema_version: self.schema_version()?,
+            last_refresh_at: self.last_refresh()?,
+            private_count,
+            public_count: repo_count - private_count,
+            orgs,
+            languages,
+            top_repos,
         })
     }
 
-    /// Store organizations in the cache (replaces existing)
+    /// Store organizations fetched from the API, keeping local usage data
     pub fn store_orgs(&self, orgs: &[Org]) -> Result<()> {
         let tx = self.conn.unchecked_transaction()?;
 
         for org in orgs {
+            // A login can move to a new id if an account was deleted and recreated
             tx.execute(
-                "INSERT OR REPLACE INTO orgs (id, login, name, avatar_url, last_accessed_at, access_count)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                params![
-                    org.id,
-                    &org.login,
-                    &org.name,
-                    &org.avatar_url,
-                    org.last_accessed_at.map(|d| d.to_rfc3339()),
-                    org.access_count,
-                ],
+                "DELETE FROM orgs WHERE login = ?1 AND id != ?2",
+                params![&org.login, org.id],
             )?;
+            tx.execute(
+                "INSERT INTO orgs (id, login, name, avatar_url)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(id) DO UPDATE SET
+                    login = excluded.login,
+                    name = excluded.name,
+                    avatar_url = excluded.avatar_url",
+                params![org.id, &org.login, &org.name, &org.avatar_url],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Insert or replace organizations including their usage data
+    pub fn upsert_orgs(&self, orgs: &[Org]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+
+        for org in orgs {
+            insert_org(&tx, org)?;
         }
 
         tx.commit()?;
         Ok(())
     }
 
-    /// Store repositories in the cache (replaces existing)
+    /// Store repositories fetched from the API, keeping local usage data
+    /// Repos whose owner is not in the new set are removed
     pub fn store_repos(&self, repos: &[Repo]) -> Result<()> {
         let tx = self.conn.unchecked_transaction()?;
+        let seen_at = Utc::now().to_rfc3339();
 
         // Delete repos whose owner_id is not in the new set
         // This prevents foreign key violations when an org is removed
@@ -162,24 +485,46 @@ impl Cache {
             tx.execute(&query, params.as_slice())?;
         }
 
+        merge_refreshed_repos(&tx, repos, &seen_at)?;
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Store a partial batch of refreshed repositories, preserving usage data
+    /// Unlike `store_repos`, repos of owners missing from the batch are kept
+    pub fn merge_repos(&self, repos: &[Repo]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        merge_refreshed_repos(&tx, repos, &Utc::now().to_rfc3339())?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Insert or replace repositories including their usage data
+    /// Unlike `store_repos`, no other rows are removed
+    pub fn upsert_repos(&self, repos: &[Repo]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+
+        for repo in repos {
+            insert_repo(&tx, repo)?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Replace every org and repo, including usage data, in a single transaction
+    /// Run history and metadata such as the last refresh time are kept
+    pub fn replace_orgs_and_repos(&self, orgs: &[Org], repos: &[Repo]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM repos", [])?;
+        tx.execute("DELETE FROM orgs", [])?;
+
+        for org in orgs {
+            insert_org(&tx, org)?;
+        }
         for repo in repos {
-            tx.execute(
-                "INSERT OR REPLACE INTO repos (id, name, full_name, owner_id, owner_login, private, description, language, default_branch, last_accessed_at, access_count)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
-                params![
-                    repo.id,
-                    &repo.name,
-                    &repo.full_name,
-                    repo.owner_id,
-                    &repo.owner_login,
-                    repo.private as i32,
-                    &repo.description,
-                    &repo.language,
-                    &repo.default_branch,
-                    repo.last_accessed_at.map(|d| d.to_rfc3339()),
-                    repo.access_count,
-                ],
-            )?;
+            insert_repo(&tx, repo)?;
         }
 
         tx.commit()?;
@@ -199,7 +544,7 @@ impl Cache {
                     login: row.get(1)?,
                     name: row.get(2)?,
                     avatar_url: row.get(3)?,
-                    last_accessed_at: row.get::<_, Option<String>>(4)?.map(|s| s.parse().unwrap()),
+                    last_accessed_at: parse_timestamp(row.get(4)?),
                     access_count: row.get(5)?,
                 })
             })?
@@ -210,70 +555,393 @@ impl Cache {
 
     /// Load all repositories from the cache
     pub fn load_repos(&self) -> Result<Vec<Repo>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, name, full_name, owner_id, owner_login, private, description, language, default_branch, last_accessed_at, access_count
-             FROM repos"
-        )?;
+        let mut stmt = self
+            .conn
+            .prepare(&format!("SELECT {} FROM repos", REPO_COLUMNS))?;
 
         let repos = stmt
-            .query_map([], |row| {
-                Ok(Repo {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    full_name: row.get(2)?,
-                    owner_id: row.get(3)?,
-                    owner_login: row.get(4)?,
-                    private: row.get::<_, i32>(5)? != 0,
-                    description: row.get(6)?,
-                    language: row.get(7)?,
-                    default_branch: row.get(8)?,
-                    last_accessed_at: row.get::<_, Option<String>>(9)?.map(|s| s.parse().unwrap()),
-                    access_count: row.get(10)?,
-                })
-            })?
+            .query_map([], repo_from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(repos)
     }
 
+    /// Record when the cache was last refreshed from the API
+    pub fn set_last_refresh(&self, at: DateTime<Utc>) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO metadata (key, value) VALUES ('last_refresh_at', ?1)",
+            params![at.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// When the cache was last refreshed from the API, if ever
+    pub fn last_refresh(&self) -> Result<Option<DateTime<Utc>>> {
+        let value: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT value FROM metadata WHERE key = 'last_refresh_at'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(value.and_then(|v| v.parse().ok()))
+    }
+
     /// Update repo access information
     pub fn record_repo_access(&self, full_name: &str) -> Result<()> {
         // Load the repo, update via domain model, then save back
-        let mut repo = self.conn.query_row(
-            "SELECT id, name, full_name, owner_id, owner_login, private, description, language, default_branch, last_accessed_at, access_count
-             FROM repos WHERE full_name = ?1",
-            params![full_name],
-            |row| {
-                Ok(Repo {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    full_name: row.get(2)?,
-                    owner_id: row.get(3)?,
-                    owner_login: row.get(4)?,
-                    private: row.get::<_, i32>(5)? != 0,
-                    description: row.get(6)?,
-                    language: row.get(7)?,
-                    default_branch: row.get(8)?,
-                    last_accessed_at: row.get::<_, Option<String>>(9)?.map(|s| s.parse().unwrap()),
-                    access_count: row.get(10)?,
-                })
-            },
-        ).optional()?.ok_or_else(|| anyhow::anyhow!("Repo not found: {}", full_name))?;
+        let mut repo = self
+            .conn
+            .query_row(
+                &format!("SELECT {} FROM repos WHERE full_name = ?1", REPO_COLUMNS),
+                params![full_name],
+                repo_from_row,
+            )
+            .optional()?
+            .ok_or_else(|| anyhow::anyhow!("Repo not found: {}", full_name))?;
 
         // Use domain model to record access
         repo.record_access();
 
-        // Save back to cache
-        self.store_repos(&[repo])?;
+        // Save back to cache without touching any other repos
+        self.upsert_repos(&[repo])
+    }
+
+    /// When the run history of `repo` was last synced and how far back it reaches
+    pub fn run_history_sync(&self, repo: &str) -> Result<Option<RunHistorySync>> {
+        let row: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT since, synced_at FROM run_history_sync WHERE repo = ?1",
+                params![repo],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+
+        Ok(row.and_then(|(since, synced_at)| {
+            Some(RunHistorySync {
+                since: parse_timestamp(Some(since))?,
+                synced_at: parse_timestamp(Some(synced_at))?,
+            })
+        }))
+    }
+
+    /// Latest cached attempt of each run of `repo`, keyed by run id
+    pub fn cached_run_attempts(&self, repo: &str) -> Result<HashMap<u64, u32>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, run_attempt FROM workflow_runs WHERE repo = ?1")?;
+        let attempts = stmt
+            .query_map(params![repo], |row| Ok((row.get::<_, i64>(0)? as u64, row.get(1)?)))?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(attempts)
+    }
+
+    /// Store completed runs of `repo`, replacing the cached jobs of each run in `jobs`
+    pub fn store_run_history(&self, repo: &str, runs: &[WorkflowRun], jobs: &[(u64, Vec<WorkflowJob>)]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+
+        for run in runs {
+            tx.execute(
+                "INSERT OR REPLACE INTO workflow_runs (id, repo, name, path, head_branch, head_sha, event, conclusion, html_url, run_attempt, created_at, updated_at, run_started_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                params![
+                    run.id as i64,
+                    repo,
+                    &run.name,
+                    &run.path,
+                    &run.head_branch,
+                    &run.head_sha,
+                    &run.event,
+                    &run.conclusion,
+                    &run.html_url,
+                    run.run_attempt,
+                    run.created_at.to_rfc3339(),
+                    run.updated_at.to_rfc3339(),
+                    run.run_started_at.map(|d| d.to_rfc3339()),
+                ],
+            )?;
+        }
+
+        for (run_id, run_jobs) in jobs {
+            tx.execute("DELETE FROM workflow_jobs WHERE run_id = ?1", params![*run_id as i64])?;
+            for job in run_jobs {
+                tx.execute(
+                    "INSERT OR REPLACE INTO workflow_jobs (id, run_id, run_attempt, name, conclusion, created_at, started_at, completed_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        job.id as i64,
+                        *run_id as i64,
+                        job.run_attempt,
+                        &job.name,
+                        &job.conclusion,
+                        job.created_at.map(|d| d.to_rfc3339()),
+                        job.started_at.map(|d| d.to_rfc3339()),
+                        job.completed_at.map(|d| d.to_rfc3339()),
+                    ],
+                )?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Record that every completed run of `repo` created since `since` is cached
+    pub fn set_run_history_synced(&self, repo: &str, since: DateTime<Utc>) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO run_history_sync (repo, since, synced_at) VALUES (?1, ?2, ?3)",
+            params![repo, since.to_rfc3339(), Utc::now().to_rfc3339()],
+        )?;
         Ok(())
     }
+
+    /// Cached runs of `repo` created since `since`, newest first, with the jobs of all their attempts
+    pub fn load_run_history(&self, repo: &str, since: DateTime<Utc>) -> Result<RunHistory> {
+        let since = since.to_rfc3339();
+
+        let runs = self
+            .conn
+            .prepare(
+                "SELECT id, name, path, head_branch, head_sha, event, conclusion, html_url, run_attempt, created_at, updated_at, run_started_at
+                 FROM workflow_runs WHERE repo = ?1 AND created_at >= ?2 ORDER BY created_at DESC",
+            )?
+            .query_map(params![repo, since], |row| {
+                Ok(WorkflowRun {
+                    id: row.get::<_, i64>(0)? as u64,
+                    name: row.get(1)?,
+                    path: row.get(2)?,
+                    head_branch: row.get(3)?,
+                    head_sha: row.get(4)?,
+                    event: row.get(5)?,
+                    status: Some("completed".to_string()),
+                    conclusion: row.get(6)?,
+                    html_url: row.get(7)?,
+                    run_attempt: row.get(8)?,
+                    created_at: required_timestamp(row, 9)?,
+                    updated_at: required_timestamp(row, 10)?,
+                    run_started_at: parse_timestamp(row.get(11)?),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let jobs = self
+            .conn
+            .prepare(
+                "SELECT j.id, j.run_id, j.run_attempt, j.name, j.conclusion, j.created_at, j.started_at, j.completed_at
+                 FROM workflow_jobs j JOIN workflow_runs r ON r.id = j.run_id
+                 WHERE r.repo = ?1 AND r.created_at >= ?2",
+            )?
+            .query_map(params![repo, since], |row| {
+                Ok(WorkflowJob {
+                    id: row.get::<_, i64>(0)? as u64,
+                    run_id: row.get::<_, i64>(1)? as u64,
+                    run_attempt: row.get(2)?,
+                    name: row.get(3)?,
+                    status: "completed".to_string(),
+                    conclusion: row.get(4)?,
+                    created_at: parse_timestamp(row.get(5)?),
+                    started_at: parse_timestamp(row.get(6)?),
+                    completed_at: parse_timestamp(row.get(7)?),
+                    steps: Vec::new(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(RunHistory { runs, jobs })
+    }
+}
+
+/// Columns selected by `repo_from_row`, in order
+const REPO_COLUMNS: &str = "id, name, full_name, owner_id, owner_login, private, description, language, default_branch, last_accessed_at, access_count, fork";
+
+/// Build a repo from a row selected with `REPO_COLUMNS`
+fn repo_from_row(row: &rusqlite::Row) -> rusqlite::Result<Repo> {
+    Ok(Repo {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        full_name: row.get(2)?,
+        owner_id: row.get(3)?,
+        owner_login: row.get(4)?,
+        private: row.get::<_, i32>(5)? != 0,
+        description: row.get(6)?,
+        language: row.get(7)?,
+        default_branch: row.get(8)?,
+        last_accessed_at: parse_timestamp(row.get(9)?),
+        access_count: row.get(10)?,
+        fork: row.get::<_, i32>(11)? != 0,
+    })
+}
+
+/// Parse a stored RFC 3339 timestamp, treating unparseable values as missing
+fn parse_timestamp(value: Option<String>) -> Option<DateTime<Utc>> {
+    value.and_then(|v| v.parse().ok())
+}
+
+/// Insert or replace a single organization row
+fn insert_org(conn: &Connection, org: &Org) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO orgs (id, login, name, avatar_url, last_accessed_at, access_count)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            org.id,
+            &org.login,
+            &org.name,
+            &org.avatar_url,
+            org.last_accessed_at.map(|d| d.to_rfc3339()),
+            org.access_count,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Upsert refreshed repos, leaving their usage data alone and marking them seen at `seen_at`
+fn merge_refreshed_repos(conn: &Connection, repos: &[Repo], seen_at: &str) -> Result<()> {
+    for repo in repos {
+        // A full_name can move to a new id if a repo was deleted and recreated
+        conn.execute(
+            "DELETE FROM repos WHERE full_name = ?1 AND id != ?2",
+            params![&repo.full_name, repo.id],
+        )?;
+        conn.execute(
+            "INSERT INTO repos (id, name, full_name, owner_id, owner_login, private, description, language, default_branch, last_seen_at, fork)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+             ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                full_name = excluded.full_name,
+                owner_id = excluded.owner_id,
+                owner_login = excluded.owner_login,
+                private = excluded.private,
+                description = excluded.description,
+                language = excluded.language,
+                default_branch = excluded.default_branch,
+                last_seen_at = excluded.last_seen_at,
+                fork = excluded.fork",
+            params![
+                repo.id,
+                &repo.name,
+                &repo.full_name,
+                repo.owner_id,
+                &repo.owner_login,
+                repo.private as i32,
+                &repo.description,
+                &repo.language,
+                &repo.default_branch,
+                seen_at,
+                repo.fork as i32,
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Read a NOT NULL timestamp column written by the cache
+fn required_timestamp(row: &rusqlite::Row, index: usize) -> rusqlite::Result<DateTime<Utc>> {
+    let value: String = row.get(index)?;
+    value.parse().map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+/// Insert or replace a single repository row
+/// Keeps the existing `last_seen_at`, marking brand new rows as seen now
+fn insert_repo(conn: &Connection, repo: &Repo) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO repos (id, name, full_name, owner_id, owner_login, private, description, language, default_branch, last_accessed_at, access_count, last_seen_at, fork)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11,
+                 COALESCE((SELECT last_seen_at FROM repos WHERE id = ?1), ?12), ?13)",
+        params![
+            repo.id,
+            &repo.name,
+            &repo.full_name,
+            repo.owner_id,
+            &repo.owner_login,
+            repo.private as i32,
+            &repo.description,
+            &repo.language,
+            &repo.default_branch,
+            repo.last_accessed_at.map(|d| d.to_rfc3339()),
+            repo.access_count,
+            Utc::now().to_rfc3339(),
+            repo.fork as i32,
+        ],
+    )?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PruneStats {
+    /// False when no refresh has run since the cutoff, so repos were left alone
+    pub repos_checked: bool,
+    pub repos_removed: usize,
+    /// Repos whose usage was last recorded before the cutoff
+    pub usage_reset: usize,
+    pub runs_removed: usize,
 }
 
+/// How much of a repo's workflow run history is cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunHistorySync {
+    /// Runs created from here on are cached
+    pub since: DateTime<Utc>,
+    pub synced_at: DateTime<Utc>,
+}
+
+/// Cached completed workflow runs and their jobs
+#[derive(Debug, Clone, Default)]
+pub struct RunHistory {
+    pub runs: Vec<WorkflowRun>,
+    pub jobs: Vec<WorkflowJob>,
+}
+
+/// Number of most used repos included in `CacheStats`
+const TOP_REPOS_COUNT: usize = 10;
+
+/// Cache statistics, serialized for `gg data status -q`
+/// The first three keys keep the names scripts have always read
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct CacheStats {
+    #[serde(rename = "orgs")]
     pub org_count: i64,
+    #[serde(rename = "repos")]
     pub repo_count: i64,
+    #[serde(rename = "size")]
     pub size_bytes: u64,
+    pub schema_version: i64,
+    pub last_refresh_at: Option<DateTime<Utc>>,
+    pub private_count: i64,
+    pub public_count: i64,
+    /// Repo counts per owner, largest first
+    #[serde(rename = "owners")]
+    pub orgs: Vec<OrgStats>,
+    /// Repo counts per primary language, largest first
+    pub languages: Vec<LanguageStats>,
+    /// Most used repos by frecency score
+    pub top_repos: Vec<RepoUsageStats>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct OrgStats {
+    pub login: String,
+    pub repo_count: i64,
+    /// Last time a refresh returned this org's repos
+    pub last_refresh_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LanguageStats {
+    pub language: String,
+    pub repo_count: i64,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RepoUsageStats {
+    pub full_name: String,
+    pub access_count: u32,
+    pub last_accessed_at: Option<DateTime<Utc>>,
+    pub score: f64,
 }
 
 #[cfg(test)]
@@ -286,9 +954,261 @@ mod tests {
             org_count: 5,
             repo_count: 42,
             size_bytes: 12345,
+            schema_version: SCHEMA_VERSION,
+            last_refresh_at: None,
+            private_count: 2,
+            public_count: 40,
+            orgs: Vec::new(),
+            languages: Vec::new(),
+            top_repos: Vec::new(),
         };
-        // Just verify it compiles for serde
-        let json = serde_json::to_string(&stats).unwrap();
-        assert!(json.contains("org_count"));
+        // `data status -q` keeps its original top-level keys
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["orgs"], 5);
+        assert_eq!(json["repos"], 42);
+        assert!(json["size"].is_u64());
+        assert!(json["owners"].is_array());
+    }
+
+    fn test_repo(id: i64, full_name: &str, owner_id: i64) -> Repo {
+        let (owner, name) = full_name.split_once('/').unwrap();
+        Repo::new(
+            id,
+            name.to_string(),
+            full_name.to_string(),
+            owner_id,
+            owner.to_string(),
+            false,
+            None,
+            Some("Rust".to_string()),
+            Some("main".to_string()),
+        )
+    }
+
+    #[test]
+    fn test_in_memory_store_and_load_repos() {
+        let cache = Cache::in_memory().unwrap();
+        let mut fork = test_repo(2, "other/b", 20);
+        fork.fork = true;
+        let repos = vec![test_repo(1, "org/a", 10), fork];
+        cache.store_repos(&repos).unwrap();
+
+        let mut loaded = cache.load_repos().unwrap();
+        loaded.sort_by_key(|r| r.id);
+        assert_eq!(loaded, repos);
+
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats.repo_count, 2);
+        assert!(stats.size_bytes > 0);
+    }
+
+    #[test]
+    fn test_detailed_stats() {
+        let cache = Cache::in_memory().unwrap();
+        let mut private = test_repo(3, "org/c", 10);
+        private.private = true;
+        private.language = None;
+        cache
+            .store_repos(&[test_repo(1, "org/a", 10), test_repo(2, "other/b", 20), private])
+            .unwrap();
+        cache.record_repo_access("other/b").unwrap();
+
+        let stats = cache.stats().unwrap();
+
+        assert_eq!((stats.private_count, stats.public_count), (1, 2));
+        assert_eq!(stats.orgs[0].login, "org");
+        assert_eq!(stats.orgs[0].repo_count, 2);
+        assert!(stats.orgs[0].last_refresh_at.is_some());
+        assert_eq!(stats.languages[0].language, "Rust");
+        assert_eq!(stats.languages[0].repo_count, 2);
+        assert_eq!(stats.languages[1].language, "Unknown");
+        assert_eq!(stats.top_repos.len(), 1);
+        assert_eq!(stats.top_repos[0].full_name, "other/b");
+    }
+
+    #[test]
+    fn test_record_repo_access_keeps_other_repos() {
+        let cache = Cache::in_memory().unwrap();
+        cache
+            .store_repos(&[test_repo(1, "org/a", 10), test_repo(2, "other/b", 20)])
+            .unwrap();
+
+        cache.record_repo_access("org/a").unwrap();
+
+        let repos = cache.load_repos().unwrap();
+        assert_eq!(repos.len(), 2);
+        let a = repos.iter().find(|r| r.full_name == "org/a").unwrap();
+        assert_eq!(a.access_count, 1);
+        assert!(a.last_accessed_at.is_some());
+    }
+
+    #[test]
+    fn test_store_repos_keeps_usage() {
+        let cache = Cache::in_memory().unwrap();
+        cache.store_repos(&[test_repo(1, "org/a", 10)]).unwrap();
+        cache.record_repo_access("org/a").unwrap();
+
+        let mut refreshed = test_repo(1, "org/a", 10);
+        refreshed.description = Some("updated".to_string());
+        cache.store_repos(&[refreshed]).unwrap();
+
+        let repos = cache.load_repos().unwrap();
+        assert_eq!(repos[0].description.as_deref(), Some("updated"));
+        assert_eq!(repos[0].access_count, 1);
+    }
+
+    #[test]
+    fn test_merge_repos_keeps_other_owners() {
+        let cache = Cache::in_memory().unwrap();
+        cache.store_repos(&[test_repo(1, "org/a", 10), test_repo(2, "other/b", 20)]).unwrap();
+        cache.record_repo_access("org/a").unwrap();
+
+        cache.merge_repos(&[test_repo(1, "org/a", 10), test_repo(3, "org/c", 10)]).unwrap();
+
+        let repos = cache.load_repos().unwrap();
+        assert_eq!(repos.len(), 3);
+        let a = repos.iter().find(|repo| repo.full_name == "org/a").unwrap();
+        assert_eq!(a.access_count, 1);
+    }
+
+    #[test]
+    fn test_replace_orgs_and_repos_keeps_metadata() {
+        let cache = Cache::in_memory().unwrap();
+        cache
+            .store_orgs(&[Org::new(10, "org".to_string(), None, None)])
+            .unwrap();
+        cache.store_repos(&[test_repo(1, "org/a", 10)]).unwrap();
+        let refreshed_at = Utc::now();
+        cache.set_last_refresh(refreshed_at).unwrap();
+
+        let mut imported = test_repo(2, "other/b", 20);
+        imported.access_count = 3;
+        let other = Org::new(20, "other".to_string(), None, None);
+        cache.replace_orgs_and_repos(&[other], &[imported.clone()]).unwrap();
+
+        assert_eq!(cache.load_repos().unwrap(), vec![imported]);
+        let orgs = cache.load_orgs().unwrap();
+        assert_eq!(orgs.len(), 1);
+        assert_eq!(orgs[0].login, "other");
+        assert_eq!(cache.last_refresh().unwrap(), Some(refreshed_at));
+    }
+
+    #[test]
+    fn test_last_refresh_roundtrip() {
+        let cache = Cache::in_memory().unwrap();
+        assert!(cache.last_refresh().unwrap().is_none());
+
+        let now = Utc::now();
+        cache.set_last_refresh(now).unwrap();
+        assert_eq!(cache.last_refresh().unwrap(), Some(now));
+    }
+
+    #[test]
+    fn test_schema_is_migrated() {
+        let cache = Cache::in_memory().unwrap();
+        assert_eq!(cache.schema_version().unwrap(), SCHEMA_VERSION);
+        assert!(cache.integrity_check().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_load_repos_ignores_corrupt_timestamps() {
+        let cache = Cache::in_memory().unwrap();
+        cache.store_repos(&[test_repo(1, "org/a", 10)]).unwrap();
+        cache
+            .conn
+            .execute("UPDATE repos SET last_accessed_at = 'garbage'", [])
+            .unwrap();
+
+        let repos = cache.load_repos().unwrap();
+        assert!(repos[0].last_accessed_at.is_none());
+    }
+
+    #[test]
+    fn test_prune_removes_unseen_repos_and_old_usage() {
+        let cache = Cache::in_memory().unwrap();
+        cache
+            .store_repos(&[
+                test_repo(1, "org/a", 10),
+                test_repo(2, "org/b", 10),
+                test_repo(3, "org/c", 10),
+            ])
+            .unwrap();
+        cache.set_last_refresh(Utc::now()).unwrap();
+        cache.record_repo_access("org/a").unwrap();
+        cache.record_repo_access("org/c").unwrap();
+
+        let long_ago = (Utc::now() - chrono::Duration::days(60)).to_rfc3339();
+        cache
+            .conn
+            .execute("UPDATE repos SET last_seen_at = ?1 WHERE id = 2", params![long_ago])
+            .unwrap();
+        cache
+            .conn
+            .execute("UPDATE repos SET last_accessed_at = ?1 WHERE id = 3", params![long_ago])
+            .unwrap();
+
+        let stats = cache
+            .prune(Utc::now() - chrono::Duration::days(30))
+            .unwrap();
+
+        assert_eq!(
+            stats,
+            PruneStats {
+                repos_checked: true,
+                repos_removed: 1,
+                usage_reset: 1,
+                runs_removed: 0,
+            }
+        );
+        let mut repos = cache.load_repos().unwrap();
+        repos.sort_by_key(|r| r.id);
+        assert_eq!(repos.len(), 2);
+        assert_eq!(repos[0].full_name, "org/a");
+        assert_eq!(repos[0].access_count, 1);
+        assert_eq!((repos[1].access_count, repos[1].last_accessed_at), (0, None));
+    }
+
+    #[test]
+    fn test_prune_keeps_repos_without_a_recent_refresh() {
+        let cache = Cache::in_memory().unwrap();
+        cache.store_repos(&[test_repo(1, "org/a", 10)]).unwrap();
+        cache.record_repo_access("org/a").unwrap();
+        let long_ago = Utc::now() - chrono::Duration::days(60);
+        cache.set_last_refresh(long_ago).unwrap();
+        cache
+            .conn
+            .execute("UPDATE repos SET last_seen_at = ?1", params![long_ago.to_rfc3339()])
+            .unwrap();
+
+        let stats = cache
+            .prune(Utc::now() - chrono::Duration::days(30))
+            .unwrap();
+
+        assert!(!stats.repos_checked);
+        assert_eq!(stats.repos_removed, 0);
+        let repos = cache.load_repos().unwrap();
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].access_count, 1);
+    }
+
+    #[test]
+    fn test_migrate_legacy_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let legacy = dir.path().join("g").join("cache.db");
+        let target = dir.path().join("cache").join("gg").join("cache.db");
+        std::fs::create_dir_all(legacy.parent().unwrap()).unwrap();
+        std::fs::write(&legacy, b"db").unwrap();
+        std::fs::write(dir.path().join("g").join("cache.db-wal"), b"wal").unwrap();
+
+        migrate_legacy_cache(&legacy, &target).unwrap();
+
+        assert!(!legacy.exists());
+        assert_eq!(std::fs::read(&target).unwrap(), b"db");
+        assert!(target.with_file_name("cache.db-wal").exists());
+
+        // A second run must not clobber the migrated cache
+        std::fs::write(&legacy, b"stale").unwrap();
+        migrate_legacy_cache(&legacy, &target).unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), b"db");
     }
 }
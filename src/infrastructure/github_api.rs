@@ -1,16 +1,164 @@
 use crate::domain::{Org, Repo};
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
 use octocrab::Octocrab;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 /// Represents a GitHub Actions workflow run
 #[derive(Debug, Clone, Deserialize)]
 pub struct WorkflowRun {
+    pub id: u64,
     pub name: String,
     pub status: Option<String>,
     pub conclusion: Option<String>,
     pub head_branch: String,
+    pub head_sha: String,
+    pub event: String,
     pub html_url: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub run_started_at: Option<DateTime<Utc>>,
+    /// Workflow file, e.g. `.github/workflows/ci.yml`
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default = "first_attempt")]
+    pub run_attempt: u32,
+}
+
+fn first_attempt() -> u32 {
+    1
+}
+
+impl WorkflowRun {
+    /// Whether the run is still queued or in progress
+    pub fn is_running(&self) -> bool {
+        self.status.as_deref() != Some("completed")
+    }
+
+    /// Time from when the run started until it finished (or until now if still running)
+    pub fn elapsed(&self) -> chrono::Duration {
+        let started = self.run_started_at.unwrap_or(self.created_at);
+        let finished = if self.is_running() {
+            Utc::now()
+        } else {
+            self.updated_at
+        };
+        (finished - started).max(chrono::Duration::zero())
+    }
+}
+
+/// A job within a workflow run
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkflowJob {
+    #[serde(default)]
+    pub id: u64,
+    #[serde(default)]
+    pub run_id: u64,
+    #[serde(default = "first_attempt")]
+    pub run_attempt: u32,
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    /// When the job was queued
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub steps: Vec<WorkflowStep>,
+}
+
+/// A single step within a workflow job
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkflowStep {
+    pub number: u32,
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// A build artifact uploaded by a workflow run
+#[derive(Debug, Clone, Deserialize)]
+pub struct Artifact {
+    pub id: u64,
+    pub name: String,
+    pub size_in_bytes: u64,
+    pub expired: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// An open pull request, as much of it as `gg watch checks` needs
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+    pub head: PullRequestHead,
+}
+
+/// The head commit of a pull request
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequestHead {
+    pub sha: String,
+}
+
+/// A check run reported by a GitHub App (GitHub Actions or third-party CI)
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckRun {
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub app: Option<CheckApp>,
+}
+
+/// The app that created a check run
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckApp {
+    pub name: String,
+}
+
+/// A commit status reported through the statuses API (older CI integrations)
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitStatus {
+    pub context: String,
+    /// `pending`, `success`, `failure` or `error`
+    pub state: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Filters for listing workflow runs
+#[derive(Debug, Clone, Default)]
+pub struct RunFilter {
+    /// Workflow file name (e.g. `ci.yml`), numeric id or workflow name
+    pub workflow: Option<String>,
+    pub branch: Option<String>,
+    /// Triggering event, e.g. `push` or `pull_request`
+    pub event: Option<String>,
+    pub head_sha: Option<String>,
+    /// Run status or conclusion, e.g. `in_progress` or `failure`
+    pub status: Option<String>,
+}
+
+/// Most runs the API returns for a filtered query, however many pages are requested
+const MAX_RUN_RESULTS: usize = 1000;
+
+/// Timestamp for a `created` range qualifier
+fn format_run_time(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Whether a `--workflow` value names a workflow file or id rather than a workflow name
+fn is_workflow_id(workflow: &str) -> bool {
+    workflow.ends_with(".yml")
+        || workflow.ends_with(".yaml")
+        || workflow.chars().all(|c| c.is_ascii_digit())
 }
 
 /// GitHub API client for fetching user data
@@ -29,6 +177,18 @@ impl GitHubClient {
         Ok(Self { client })
     }
 
+    /// Create a client that talks to a different API host (used to test against a mock server)
+    #[cfg(test)]
+    pub fn with_base_url(token: String, base_url: &str) -> Result<Self> {
+        let client = Octocrab::builder()
+            .personal_token(token)
+            .base_uri(base_url)?
+            .build()
+            .context("Failed to create GitHub client")?;
+
+        Ok(Self { client })
+    }
+
     /// Fetch all organizations for the authenticated user
     pub async fn fetch_orgs(&self) -> Result<Vec<Org>> {
         let mut orgs = Vec::new();
@@ -92,24 +252,67 @@ impl GitHubClient {
     /// Skips archived repositories
     pub async fn fetch_repos(&self) -> Result<Vec<Repo>> {
         let mut repos = Vec::new();
+        self.fetch_repos_paged(|page| repos.extend_from_slice(page))
+            .await?;
+        Ok(repos)
+    }
+
+    /// Fetch all repositories like `fetch_repos`, handing each page to
+    /// `on_page` as soon as it arrives
+    pub async fn fetch_repos_paged(&self, mut on_page: impl FnMut(&[Repo])) -> Result<()> {
         let mut seen_ids = std::collections::HashSet::new();
 
         // First fetch user's personal repos
+        self.fetch_repo_pages(
+            "/user/repos?sort=updated&type=all",
+            "Failed to fetch user repositories".to_string(),
+            &mut seen_ids,
+            &mut on_page,
+        )
+        .await?;
+
+        // Then fetch repos for each organization
+        let current_user = self.client.current().user().await?;
+        let orgs = self.fetch_orgs().await?;
+        for org in &orgs {
+            // Skip the user's personal login as we already fetched those repos
+            if org.login == current_user.login {
+                continue;
+            }
+
+            self.fetch_repo_pages(
+                &format!("/orgs/{}/repos?sort=updated&type=all", org.login),
+                format!("Failed to fetch repositories for org {}", org.login),
+                &mut seen_ids,
+                &mut on_page,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Walk every page of a repository listing endpoint
+    async fn fetch_repo_pages(
+        &self,
+        path: &str,
+        error_context: String,
+        seen_ids: &mut std::collections::HashSet<i64>,
+        on_page: &mut impl FnMut(&[Repo]),
+    ) -> Result<()> {
         let mut page = 1u32;
         loop {
             let page_repos: Vec<octocrab::models::Repository> = self
                 .client
                 .get(
-                    format!(
-                        "/user/repos?page={}&per_page=100&sort=updated&type=all",
-                        page
-                    ),
+                    format!("{}&page={}&per_page=100", path, page),
                     None::<&()>,
                 )
                 .await
-                .context("Failed to fetch user repositories")?;
+                .with_context(|| error_context.clone())?;
 
             let count = page_repos.len();
+            let mut repos = Vec::with_capacity(count);
 
             for repo in page_repos {
                 // Skip archived repos
@@ -128,7 +331,7 @@ impl GitHubClient {
                 let owner_id = owner.id.0 as i64;
                 let owner_login = owner.login;
 
-                repos.push(Repo::new(
+                let mut cached = Repo::new(
                     repo.id.0 as i64,
                     repo.name.clone(),
                     repo.full_name
@@ -142,7 +345,13 @@ impl GitHubClient {
                         _ => None,
                     }),
                     repo.default_branch,
-                ));
+                );
+                cached.fork = repo.fork.unwrap_or(false);
+                repos.push(cached);
+            }
+
+            if !repos.is_empty() {
+                on_page(&repos);
             }
 
             if count < 100 {
@@ -152,155 +361,723 @@ impl GitHubClient {
             page += 1;
         }
 
-        // Then fetch repos for each organization
-        let orgs = self.fetch_orgs().await?;
-        for org in &orgs {
-            // Skip the user's personal login as we already fetched those repos
-            let current_user = self.client.current().user().await?;
-            if org.login == current_user.login {
-                continue;
+        Ok(())
+    }
+
+    /// Fetch the most recent workflow runs for a repository matching `filter`, newest first
+    pub async fn fetch_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        filter: &RunFilter,
+        limit: u8,
+    ) -> Result<Vec<WorkflowRun>> {
+        #[derive(Serialize)]
+        struct Params<'a> {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            branch: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            event: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            head_sha: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            status: Option<&'a str>,
+            per_page: u8,
+        }
+
+        #[derive(Deserialize)]
+        struct WorkflowRunsResponse {
+            workflow_runs: Vec<WorkflowRun>,
+        }
+
+        let params = Params {
+            branch: filter.branch.as_deref(),
+            event: filter.event.as_deref(),
+            head_sha: filter.head_sha.as_deref(),
+            status: filter.status.as_deref(),
+            per_page: limit,
+        };
+
+        // Each workflow has its own endpoint, so names are looked up as ids first
+        // rather than filtering a page of every workflow's runs
+        let workflow_id = match filter.workflow.as_deref() {
+            Some(workflow) if is_workflow_id(workflow) => Some(workflow.to_string()),
+            Some(name) => Some(self.find_workflow_id(owner, repo, name).await?.to_string()),
+            None => None,
+        };
+        let url = match workflow_id {
+            Some(id) => format!("/repos/{}/{}/actions/workflows/{}/runs", owner, repo, id),
+            None => format!("/repos/{}/{}/actions/runs", owner, repo),
+        };
+
+        let response: WorkflowRunsResponse = self
+            .client
+            .get(&url, Some(&params))
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to fetch workflow runs for {}/{}",
+                    owner, repo
+                )
+            })?;
+
+        Ok(response.workflow_runs)
+    }
+
+    /// Id of the workflow called `name` (ignoring case) in a repository
+    async fn find_workflow_id(&self, owner: &str, repo: &str, name: &str) -> Result<u64> {
+        #[derive(Deserialize)]
+        struct Workflow {
+            id: u64,
+            name: String,
+        }
+
+        #[derive(Deserialize)]
+        struct WorkflowsResponse {
+            workflows: Vec<Workflow>,
+        }
+
+        const PER_PAGE: usize = 100;
+        let mut page = 1u32;
+        loop {
+            let response: WorkflowsResponse = self
+                .client
+                .get(
+                    format!("/repos/{}/{}/actions/workflows", owner, repo),
+                    Some(&[("per_page", PER_PAGE as u32), ("page", page)]),
+                )
+                .await
+                .with_context(|| format!("Failed to fetch workflows for {}/{}", owner, repo))?;
+
+            if let Some(workflow) = response.workflows.iter().find(|w| w.name.eq_ignore_ascii_case(name)) {
+                return Ok(workflow.id);
+            }
+            if response.workflows.len() < PER_PAGE {
+                bail!("No workflow named `{}` in {}/{}", name, owner, repo);
             }
+            page += 1;
+        }
+    }
 
-            let mut page = 1u32;
-            loop {
-                let page_repos: Vec<octocrab::models::Repository> = self
-                    .client
-                    .get(
-                        format!(
-                            "/orgs/{}/repos?page={}&per_page=100&sort=updated&type=all",
-                            org.login, page
-                        ),
-                        None::<&()>,
-                    )
-                    .await
-                    .with_context(|| {
-                        format!("Failed to fetch repositories for org {}", org.login)
-                    })?;
-
-                let count = page_repos.len();
-
-                for repo in page_repos {
-                    // Skip archived repos
-                    if repo.archived.unwrap_or(false) {
-                        continue;
-                    }
-
-                    // Skip duplicates
-                    if !seen_ids.insert(repo.id.0 as i64) {
-                        continue;
-                    }
-
-                    let owner = repo
-                        .owner
-                        .ok_or_else(|| anyhow::anyhow!("Repo missing owner"))?;
-                    let owner_id = owner.id.0 as i64;
-                    let owner_login = owner.login;
-
-                    repos.push(Repo::new(
-                        repo.id.0 as i64,
-                        repo.name.clone(),
-                        repo.full_name
-                            .unwrap_or_else(|| format!("{}/{}", owner_login, repo.name)),
-                        owner_id,
-                        owner_login,
-                        repo.private.unwrap_or(false),
-                        repo.description.as_ref().map(|d| d.to_string()),
-                        repo.language.as_ref().and_then(|l| match l {
-                            serde_json::Value::String(s) => Some(s.clone()),
-                            _ => None,
-                        }),
-                        repo.default_branch,
-                    ));
-                }
+    /// Fetch a single workflow run by id
+    pub async fn fetch_workflow_run(&self, owner: &str, repo: &str, run_id: u64) -> Result<WorkflowRun> {
+        self.client
+            .get(
+                format!("/repos/{}/{}/actions/runs/{}", owner, repo, run_id),
+                None::<&()>,
+            )
+            .await
+            .with_context(|| format!("Failed to fetch workflow run {}", run_id))
+    }
 
-                if count < 100 {
-                    break;
-                }
+    /// Fetch all completed workflow runs created on or after `since`, newest first
+    /// The API returns at most 1000 runs for a filtered query, so when a query hits
+    /// that cap the rest are fetched with another query ending at the oldest run seen
+    pub async fn fetch_run_history(&self, owner: &str, repo: &str, since: NaiveDate) -> Result<Vec<WorkflowRun>> {
+        let start = format_run_time(since.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+        let mut runs = Vec::new();
+        let mut seen = HashSet::new();
+        let mut until: Option<DateTime<Utc>> = None;
+        loop {
+            let created = match until {
+                Some(until) => format!("{}..{}", start, format_run_time(until)),
+                None => format!(">={}", start),
+            };
+            let (batch, capped) = self.fetch_run_history_range(owner, repo, created).await?;
+            let oldest = batch.iter().map(|run| run.created_at).min();
+            // Runs created at the boundary come back in both queries
+            runs.extend(batch.into_iter().filter(|run| seen.insert(run.id)));
 
-                page += 1;
+            match oldest {
+                Some(oldest) if capped && until != Some(oldest) => until = Some(oldest),
+                Some(oldest) if capped => bail!(
+                    "More than {} workflow runs were created at {} in {}/{}",
+                    MAX_RUN_RESULTS,
+                    oldest,
+                    owner,
+                    repo
+                ),
+                _ => break,
             }
         }
 
-        Ok(repos)
+        Ok(runs)
     }
 
-    /// Fetch workflow runs for a repository, optionally filtered by branch
-    /// Returns the most recent run, prioritizing runs on the specified branch
-    pub async fn fetch_workflow_runs(
+    /// Fetch completed runs matching a `created` range, newest first
+    /// Returns whether the API's result cap was hit, leaving older runs out
+    async fn fetch_run_history_range(
         &self,
         owner: &str,
         repo: &str,
-        branch: Option<&str>,
-    ) -> Result<Option<WorkflowRun>> {
-        let mut url = format!("/repos/{}/{}/actions/runs", owner, repo);
-
-        // Add branch filter if specified
-        if let Some(branch_name) = branch {
-            url.push_str(&format!("?branch={}", branch_name));
+        created: String,
+    ) -> Result<(Vec<WorkflowRun>, bool)> {
+        #[derive(Serialize)]
+        struct Params<'a> {
+            created: &'a str,
+            status: &'static str,
+            per_page: usize,
+            page: usize,
         }
 
         #[derive(Deserialize)]
         struct WorkflowRunsResponse {
-            workflow_runs: Vec<WorkflowRunResponse>,
+            workflow_runs: Vec<WorkflowRun>,
+        }
+
+        const PER_PAGE: usize = 100;
+        let mut runs = Vec::new();
+        for page in 1..=MAX_RUN_RESULTS / PER_PAGE {
+            let params = Params {
+                created: &created,
+                status: "completed",
+                per_page: PER_PAGE,
+                page,
+            };
+            let response: WorkflowRunsResponse = self
+                .client
+                .get(format!("/repos/{}/{}/actions/runs", owner, repo), Some(&params))
+                .await
+                .with_context(|| format!("Failed to fetch workflow run history for {}/{}", owner, repo))?;
+
+            let count = response.workflow_runs.len();
+            runs.extend(response.workflow_runs);
+            if count < PER_PAGE {
+                return Ok((runs, false));
+            }
+        }
+
+        Ok((runs, true))
+    }
+
+    /// Fetch all jobs (with their steps) for the latest attempt of a workflow run
+    pub async fn fetch_run_jobs(&self, owner: &str, repo: &str, run_id: u64) -> Result<Vec<WorkflowJob>> {
+        self.fetch_jobs(owner, repo, run_id, false).await
+    }
+
+    /// Fetch the jobs of every attempt of a workflow run
+    pub async fn fetch_run_job_history(&self, owner: &str, repo: &str, run_id: u64) -> Result<Vec<WorkflowJob>> {
+        self.fetch_jobs(owner, repo, run_id, true).await
+    }
+
+    async fn fetch_jobs(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+        all_attempts: bool,
+    ) -> Result<Vec<WorkflowJob>> {
+        #[derive(Deserialize)]
+        struct JobsResponse {
+            jobs: Vec<WorkflowJob>,
         }
 
+        let filter = if all_attempts { "filter=all&" } else { "" };
+        let mut jobs = Vec::new();
+        let mut page = 1u32;
+        loop {
+            let response: JobsResponse = self
+                .client
+                .get(
+                    format!(
+                        "/repos/{}/{}/actions/runs/{}/jobs?{}page={}&per_page=100",
+                        owner, repo, run_id, filter, page
+                    ),
+                    None::<&()>,
+                )
+                .await
+                .with_context(|| format!("Failed to fetch jobs for workflow run {}", run_id))?;
+
+            let count = response.jobs.len();
+            jobs.extend(response.jobs);
+
+            if count < 100 {
+                break;
+            }
+
+            page += 1;
+        }
+
+        Ok(jobs)
+    }
+
+    /// Download the zip archive with the logs of every job in a workflow run
+    pub async fn download_run_logs(&self, owner: &str, repo: &str, run_id: u64) -> Result<Vec<u8>> {
+        let bytes = self
+            .client
+            .actions()
+            .download_workflow_run_logs(owner, repo, run_id.into())
+            .await
+            .with_context(|| format!("Failed to download logs for workflow run {}", run_id))?;
+
+        Ok(bytes.to_vec())
+    }
+
+    /// Fetch all artifacts uploaded by a workflow run
+    pub async fn fetch_run_artifacts(&self, owner: &str, repo: &str, run_id: u64) -> Result<Vec<Artifact>> {
         #[derive(Deserialize)]
-        struct WorkflowRunResponse {
-            name: String,
-            status: Option<String>,
-            conclusion: Option<String>,
-            head_branch: String,
-            html_url: String,
+        struct ArtifactsResponse {
+            artifacts: Vec<Artifact>,
         }
 
-        let response: WorkflowRunsResponse = self
+        let mut artifacts = Vec::new();
+        let mut page = 1u32;
+        loop {
+            let response: ArtifactsResponse = self
+                .client
+                .get(
+                    format!(
+                        "/repos/{}/{}/actions/runs/{}/artifacts?page={}&per_page=100",
+                        owner, repo, run_id, page
+                    ),
+                    None::<&()>,
+                )
+                .await
+                .with_context(|| format!("Failed to fetch artifacts for workflow run {}", run_id))?;
+
+            let count = response.artifacts.len();
+            artifacts.extend(response.artifacts);
+
+            if count < 100 {
+                break;
+            }
+
+            page += 1;
+        }
+
+        Ok(artifacts)
+    }
+
+    /// Download an artifact's zip archive
+    pub async fn download_artifact(&self, owner: &str, repo: &str, artifact_id: u64) -> Result<Vec<u8>> {
+        let bytes = self
             .client
-            .get(&url, None::<&()>)
+            .actions()
+            .download_artifact(
+                owner,
+                repo,
+                artifact_id.into(),
+                octocrab::params::actions::ArchiveFormat::Zip,
+            )
             .await
-            .with_context(|| {
-                format!(
-                    "Failed to fetch workflow runs for {}/{}",
-                    owner, repo
+            .with_context(|| format!("Failed to download artifact {}", artifact_id))?;
+
+        Ok(bytes.to_vec())
+    }
+
+    /// Find the open pull request whose head is `branch` in the same repository
+    pub async fn fetch_branch_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<Option<PullRequest>> {
+        #[derive(Serialize)]
+        struct Params<'a> {
+            head: String,
+            state: &'a str,
+        }
+
+        let pulls: Vec<PullRequest> = self
+            .client
+            .get(
+                format!("/repos/{}/{}/pulls", owner, repo),
+                Some(&Params {
+                    head: format!("{}:{}", owner, branch),
+                    state: "open",
+                }),
+            )
+            .await
+            .with_context(|| format!("Failed to find a pull request for branch {}", branch))?;
+
+        Ok(pulls.into_iter().next())
+    }
+
+    /// Fetch all check runs for a commit
+    pub async fn fetch_check_runs(&self, owner: &str, repo: &str, sha: &str) -> Result<Vec<CheckRun>> {
+        #[derive(Deserialize)]
+        struct CheckRunsResponse {
+            check_runs: Vec<CheckRun>,
+        }
+
+        let mut check_runs = Vec::new();
+        let mut page = 1u32;
+        loop {
+            let response: CheckRunsResponse = self
+                .client
+                .get(
+                    format!(
+                        "/repos/{}/{}/commits/{}/check-runs?page={}&per_page=100",
+                        owner, repo, sha, page
+                    ),
+                    None::<&()>,
                 )
-            })?;
+                .await
+                .with_context(|| format!("Failed to fetch check runs for {}", sha))?;
+
+            let count = response.check_runs.len();
+            check_runs.extend(response.check_runs);
 
-        if response.workflow_runs.is_empty() {
-            return Ok(None);
+            if count < 100 {
+                break;
+            }
+
+            page += 1;
         }
 
-        // GitHub API returns runs ordered by most recent first
-        // Convert to our internal type
-        let runs: Vec<WorkflowRun> = response
-            .workflow_runs
-            .into_iter()
-            .map(|r| WorkflowRun {
-                name: r.name,
-                status: r.status,
-                conclusion: r.conclusion,
-                head_branch: r.head_branch,
-                html_url: r.html_url,
-            })
-            .collect();
-
-        // Find the first in_progress or queued run
-        let running = runs.iter().find(|r| {
-            r.status
-                .as_ref()
-                .map(|s| s == "in_progress" || s == "queued")
-                .unwrap_or(false)
-        });
-
-        if let Some(run) = running {
-            return Ok(Some(run.clone()));
+        Ok(check_runs)
+    }
+
+    /// Fetch the latest commit status for each context on a commit
+    pub async fn fetch_commit_statuses(&self, owner: &str, repo: &str, sha: &str) -> Result<Vec<CommitStatus>> {
+        #[derive(Deserialize)]
+        struct CombinedStatus {
+            statuses: Vec<CommitStatus>,
         }
 
-        // Otherwise return the most recent completed run
-        Ok(Some(runs[0].clone()))
+        let response: CombinedStatus = self
+            .client
+            .get(
+                format!("/repos/{}/{}/commits/{}/status?per_page=100", owner, repo, sha),
+                None::<&()>,
+            )
+            .await
+            .with_context(|| format!("Failed to fetch commit statuses for {}", sha))?;
+
+        Ok(response.statuses)
+    }
+
+    /// Re-run a completed workflow run, or only its failed jobs
+    pub async fn rerun_workflow_run(&self, owner: &str, repo: &str, run_id: u64, failed_only: bool) -> Result<()> {
+        let action = if failed_only { "rerun-failed-jobs" } else { "rerun" };
+        let response = self
+            .client
+            ._post(
+                format!("/repos/{}/{}/actions/runs/{}/{}", owner, repo, run_id, action),
+                None::<&()>,
+            )
+            .await
+            .with_context(|| format!("Failed to re-run workflow run {}", run_id))?;
+
+        octocrab::map_github_error(response)
+            .await
+            .with_context(|| format!("Failed to re-run workflow run {}", run_id))?;
+        Ok(())
+    }
+
+    /// Cancel a queued or in-progress workflow run
+    pub async fn cancel_workflow_run(&self, owner: &str, repo: &str, run_id: u64) -> Result<()> {
+        self.client
+            .actions()
+            .cancel_workflow_run(owner, repo, run_id.into())
+            .await
+            .with_context(|| format!("Failed to cancel workflow run {}", run_id))
+    }
+
+    /// Trigger a `workflow_dispatch` event for a workflow file on the given ref
+    pub async fn dispatch_workflow(
+        &self,
+        owner: &str,
+        repo: &str,
+        workflow_file: &str,
+        git_ref: &str,
+        inputs: serde_json::Value,
+    ) -> Result<()> {
+        self.client
+            .actions()
+            .create_workflow_dispatch(owner, repo, workflow_file, git_ref)
+            .inputs(inputs)
+            .send()
+            .await
+            .with_context(|| format!("Failed to dispatch workflow {} on {}", workflow_file, git_ref))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    fn run_json(id: u64, status: &str, conclusion: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "name": "CI",
+            "status": status,
+            "conclusion": conclusion,
+            "head_branch": "main",
+            "head_sha": "abc123",
+            "event": "push",
+            "html_url": format!("https://github.com/octocat/hello/actions/runs/{}", id),
+            "run_attempt": 1,
+            "created_at": "2026-01-01T10:00:00Z",
+            "updated_at": "2026-01-01T10:05:00Z",
+            "run_started_at": "2026-01-01T10:00:30Z"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_fetch_run_history_splits_capped_queries() {
+        let mut server = mockito::Server::new_async().await;
+        let query = |created: &str, page: usize| {
+            mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("created".into(), created.into()),
+                mockito::Matcher::UrlEncoded("page".into(), page.to_string()),
+            ])
+        };
+
+        // The first query hits the 1000 run cap, the oldest run was created on Jan 5th
+        let mut capped = Vec::new();
+        for page in 1..=10 {
+            let runs: Vec<_> = (0..100)
+                .map(|i| {
+                    let id = 2000 - (page * 100 + i) as u64;
+                    let mut run = run_json(id, "completed", Some("success"));
+                    let day = if page == 10 && i == 99 { 5 } else { 6 };
+                    run["created_at"] = format!("2026-01-0{}T10:00:00Z", day).into();
+                    run
+                })
+                .collect();
+            capped.push(
+                server
+                    .mock("GET", "/repos/octocat/hello/actions/runs")
+                    .match_query(query(">=2026-01-01T00:00:00Z", page))
+                    .with_header("content-type", "application/json")
+                    .with_body(serde_json::json!({"workflow_runs": runs}).to_string())
+                    .create_async()
+                    .await,
+            );
+        }
+        let older = server
+            .mock("GET", "/repos/octocat/hello/actions/runs")
+            .match_query(query("2026-01-01T00:00:00Z..2026-01-05T10:00:00Z", 1))
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({"workflow_runs": [
+                    run_json(1001, "completed", Some("success")),
+                    run_json(7, "completed", Some("failure")),
+                ]})
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let client = GitHubClient::with_base_url("token".to_string(), &server.url()).unwrap();
+        let since = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let runs = client.fetch_run_history("octocat", "hello", since).await.unwrap();
+
+        assert_eq!(runs.len(), 1001);
+        assert_eq!(runs.last().unwrap().id, 7);
+        for mock in capped {
+            mock.assert_async().await;
+        }
+        older.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_fetch_run_jobs() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/repos/octocat/hello/actions/runs/42/jobs?page=1&per_page=100")
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({
+                    "total_count": 1,
+                    "jobs": [{
+                        "id": 7,
+                        "name": "test",
+                        "status": "in_progress",
+                        "conclusion": null,
+                        "started_at": "2026-01-01T10:01:00Z",
+                        "completed_at": null,
+                        "steps": [
+                            {"number": 1, "name": "Set up job", "status": "completed", "conclusion": "success",
+                             "started_at": "2026-01-01T10:01:00Z", "completed_at": "2026-01-01T10:01:02Z"},
+                            {"number": 2, "name": "Run tests", "status": "in_progress", "conclusion": null,
+                             "started_at": "2026-01-01T10:01:02Z", "completed_at": null}
+                        ]
+                    }]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let client = GitHubClient::with_base_url("token".to_string(), &server.url()).unwrap();
+        let jobs = client.fetch_run_jobs("octocat", "hello", 42).await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].steps.len(), 2);
+        assert_eq!(jobs[0].steps[1].status, "in_progress");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_workflow_run() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/repos/octocat/hello/actions/runs/42")
+            .with_header("content-type", "application/json")
+            .with_body(run_json(42, "completed", Some("success")).to_string())
+            .create_async()
+            .await;
+
+        let client = GitHubClient::with_base_url("token".to_string(), &server.url()).unwrap();
+        let run = client.fetch_workflow_run("octocat", "hello", 42).await.unwrap();
+
+        assert_eq!(run.id, 42);
+        assert!(!run.is_running());
+        assert_eq!(run.elapsed(), chrono::Duration::seconds(270));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_workflow_runs_filters() {
+        let mut server = mockito::Server::new_async().await;
+        let by_file = server
+            .mock("GET", "/repos/octocat/hello/actions/workflows/ci.yml/runs")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("branch".into(), "feat/a+b".into()),
+                mockito::Matcher::UrlEncoded("event".into(), "push".into()),
+                mockito::Matcher::UrlEncoded("per_page".into(), "5".into()),
+            ]))
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::json!({"workflow_runs": [run_json(1, "completed", Some("success"))]}).to_string())
+            .create_async()
+            .await;
+        let mut deploy = run_json(3, "completed", Some("success"));
+        deploy["name"] = "Deploy".into();
+        let workflows = server
+            .mock("GET", "/repos/octocat/hello/actions/workflows")
+            .match_query(mockito::Matcher::Any)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!({"workflows": [
+                    {"id": 7, "name": "CI"},
+                    {"id": 9, "name": "Deploy"},
+                ]})
+                .to_string(),
+            )
+            .expect(2)
+            .create_async()
+            .await;
+        let by_name = server
+            .mock("GET", "/repos/octocat/hello/actions/workflows/9/runs")
+            .match_query(mockito::Matcher::UrlEncoded("head_sha".into(), "abc123".into()))
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::json!({"workflow_runs": [deploy]}).to_string())
+            .create_async()
+            .await;
+
+        let client = GitHubClient::with_base_url("token".to_string(), &server.url()).unwrap();
+        let filter = RunFilter {
+            workflow: Some("ci.yml".to_string()),
+            branch: Some("feat/a+b".to_string()),
+            event: Some("push".to_string()),
+            ..Default::default()
+        };
+        let runs = client.fetch_workflow_runs("octocat", "hello", &filter, 5).await.unwrap();
+        assert_eq!(runs.len(), 1);
+
+        let filter = RunFilter {
+            workflow: Some("deploy".to_string()),
+            head_sha: Some("abc123".to_string()),
+            ..Default::default()
+        };
+        let runs = client.fetch_workflow_runs("octocat", "hello", &filter, 5).await.unwrap();
+        assert_eq!(runs.iter().map(|r| r.id).collect::<Vec<_>>(), vec![3]);
+
+        let filter = RunFilter {
+            workflow: Some("Release".to_string()),
+            ..Default::default()
+        };
+        assert!(client.fetch_workflow_runs("octocat", "hello", &filter, 5).await.is_err());
+
+        by_file.assert_async().await;
+        workflows.assert_async().await;
+        by_name.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_fetch_branch_pull_request() {
+        let mut server = mockito::Server::new_async().await;
+        let pulls = server
+            .mock("GET", "/repos/octocat/hello/pulls")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("head".into(), "octocat:feat/a+b".into()),
+                mockito::Matcher::UrlEncoded("state".into(), "open".into()),
+            ]))
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::json!([{
+                    "number": 12, "title": "Add checks", "head": {"sha": "abc123"},
+                    "html_url": "https://github.com/octocat/hello/pull/12"
+                }])
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let client = GitHubClient::with_base_url("token".to_string(), &server.url()).unwrap();
+        let pr = client
+            .fetch_branch_pull_request("octocat", "hello", "feat/a+b")
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(pr.number, 12);
+        assert_eq!(pr.head.sha, "abc123");
+        pulls.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_rerun_failed_jobs() {
+        let mut server = mockito::Server::new_async().await;
+        let rerun = server
+            .mock("POST", "/repos/octocat/hello/actions/runs/42/rerun-failed-jobs")
+            .with_status(201)
+            .create_async()
+            .await;
+        let conflict = server
+            .mock("POST", "/repos/octocat/hello/actions/runs/43/rerun")
+            .with_status(403)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"message": "This workflow is already running"}"#)
+            .create_async()
+            .await;
+
+        let client = GitHubClient::with_base_url("token".to_string(), &server.url()).unwrap();
+        client.rerun_workflow_run("octocat", "hello", 42, true).await.unwrap();
+        assert!(client.rerun_workflow_run("octocat", "hello", 43, false).await.is_err());
+
+        rerun.assert_async().await;
+        conflict.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_workflow() {
+        let mut server = mockito::Server::new_async().await;
+        let dispatch = server
+            .mock("POST", "/repos/octocat/hello/actions/workflows/deploy.yml/dispatches")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "ref": "main",
+                "inputs": {"environment": "staging"}
+            })))
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let client = GitHubClient::with_base_url("token".to_string(), &server.url()).unwrap();
+        client
+            .dispatch_workflow(
+                "octocat",
+                "hello",
+                "deploy.yml",
+                "main",
+                serde_json::json!({"environment": "staging"}),
+            )
+            .await
+            .unwrap();
+
+        dispatch.assert_async().await;
+    }
 
     #[test]
     fn test_owner_map_logic() {
@@ -1,5 +1,12 @@
 pub mod cache;
 pub mod github_api;
+pub mod settings;
+pub mod workflow_files;
 
-pub use cache::{cache_path, Cache};
-pub use github_api::GitHubClient;
+pub use cache::{cache_path, Cache, CacheStats, RunHistory, RunHistorySync};
+pub use github_api::{
+    Artifact, CheckRun, CommitStatus, GitHubClient, RunFilter, WorkflowJob, WorkflowRun,
+    WorkflowStep,
+};
+pub use settings::{load_settings, BindingAction, BindingTarget, KeyBindingConfig, Settings};
+pub use workflow_files::{load_workflows, WorkflowFile};
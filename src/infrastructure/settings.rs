@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// User settings read from `config.yaml` in the gg config directory
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// Extra TUI key bindings, overriding the built-in ones on the same key
+    pub keybindings: Vec<KeyBindingConfig>,
+    /// Where the TUI's action menu clones repos, a template like `~/src/{owner}/{name}`
+    pub clone_path: Option<String>,
+}
+
+/// A key binding from the settings file, e.g.
+/// `{ key: ctrl+k, open: "{url}/wiki", description: Wiki }`
+#[derive(Debug, Clone, Deserialize)]
+pub struct KeyBindingConfig {
+    /// Key chord such as `ctrl+k`, `alt+y` or `f5`
+    pub key: String,
+    /// Items the binding applies to
+    #[serde(default)]
+    pub on: BindingTarget,
+    /// Shown in the help popup, defaults to the action's template
+    pub description: Option<String>,
+    #[serde(flatten)]
+    pub action: BindingAction,
+}
+
+/// What a configured key does, each with a template like `{url}/tree/{default_branch}`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BindingAction {
+    /// Open the expanded URL in the browser
+    Open(String),
+    /// Copy the expanded text to the clipboard
+    Copy(String),
+    /// Leave the TUI and run the expanded shell command
+    Run(String),
+}
+
+/// Which list items a binding applies to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BindingTarget {
+    #[default]
+    Repo,
+    Org,
+    Any,
+}
+
+/// Settings file location, `override_path` or `config.yaml` in the XDG config directory
+pub fn settings_path(override_path: Option<&Path>) -> Result<PathBuf> {
+    if let Some(path) = override_path {
+        return Ok(path.to_path_buf());
+    }
+    let base_dir = dirs::config_dir().context("Could not determine config directory")?;
+    Ok(base_dir.join("gg").join("config.yaml"))
+}
+
+/// Load the settings file, using the defaults when it doesn't exist
+pub fn load_settings(override_path: Option<&Path>) -> Result<Settings> {
+    let path = settings_path(override_path)?;
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && override_path.is_none() => {
+            return Ok(Settings::default());
+        }
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read settings from {}", path.display()));
+        }
+    };
+    // An empty file (or one with only comments) parses as null
+    if contents.trim().is_empty() {
+        return Ok(Settings::default());
+    }
+    serde_norway::from_str(&contents).with_context(|| format!("Invalid settings in {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        std::fs::write(
+            &path,
+            r#"
+clone_path: "~/code/{owner}/{name}"
+keybindings:
+  - key: ctrl+k
+    open: "{url}/wiki"
+    description: Wiki
+  - key: alt+y
+    on: any
+    copy: "{full_name}"
+"#,
+        )
+        .unwrap();
+
+        let settings = load_settings(Some(&path)).unwrap();
+
+        assert_eq!(settings.clone_path.as_deref(), Some("~/code/{owner}/{name}"));
+        assert_eq!(settings.keybindings.len(), 2);
+        assert_eq!(settings.keybindings[0].on, BindingTarget::Repo);
+        assert_eq!(
+            settings.keybindings[0].action,
+            BindingAction::Open("{url}/wiki".to_string())
+        );
+        assert_eq!(settings.keybindings[1].on, BindingTarget::Any);
+
+        std::fs::write(&path, "keybindings:\n  - key: ctrl+k\n    launch: rockets\n").unwrap();
+        assert!(load_settings(Some(&path)).is_err());
+        assert!(load_settings(Some(&dir.path().join("missing.yaml"))).is_err());
+    }
+}
@@ -0,0 +1,285 @@
+use anyhow::{bail, Context, Result};
+use serde_norway::Value;
+use std::path::Path;
+
+/// A workflow definition from `.github/workflows`
+#[derive(Debug, Clone)]
+pub struct WorkflowFile {
+    /// File name, e.g. `deploy.yml` (what the API accepts as a workflow id)
+    pub file_name: String,
+    /// The workflow's `name:`, if it sets one
+    pub name: Option<String>,
+    /// Inputs accepted by `workflow_dispatch`, or None if it can't be dispatched
+    pub dispatch_inputs: Option<Vec<DispatchInput>>,
+}
+
+/// A `workflow_dispatch` input
+#[derive(Debug, Clone, PartialEq)]
+pub struct DispatchInput {
+    pub name: String,
+    pub description: Option<String>,
+    pub required: bool,
+    pub default: Option<String>,
+    /// `string`, `boolean`, `choice`, `number` or `environment`
+    pub input_type: String,
+    /// Allowed values for `choice` inputs
+    pub options: Vec<String>,
+}
+
+impl WorkflowFile {
+    /// Parse a workflow file's YAML
+    pub fn parse(file_name: &str, yaml: &str) -> Result<Self> {
+        let doc: Value = serde_norway::from_str(yaml)
+            .with_context(|| format!("Failed to parse workflow {}", file_name))?;
+
+        let name = doc.get("name").and_then(Value::as_str).map(str::to_string);
+        let dispatch_inputs = doc.get("on").and_then(dispatch_inputs);
+
+        Ok(Self {
+            file_name: file_name.to_string(),
+            name,
+            dispatch_inputs,
+        })
+    }
+
+    /// Whether `query` names this workflow, by file name (with or without extension) or `name:`
+    pub fn matches(&self, query: &str) -> bool {
+        let stem = self
+            .file_name
+            .rsplit_once('.')
+            .map_or(self.file_name.as_str(), |(stem, _)| stem);
+        self.file_name.eq_ignore_ascii_case(query)
+            || stem.eq_ignore_ascii_case(query)
+            || self
+                .name
+                .as_deref()
+                .is_some_and(|name| name.eq_ignore_ascii_case(query))
+    }
+
+    /// Check `-f key=value` fields against the dispatch inputs and build the API payload
+    pub fn validate_inputs(&self, fields: &[(String, String)]) -> Result<serde_json::Value> {
+        let Some(inputs) = &self.dispatch_inputs else {
+            bail!("Workflow {} has no workflow_dispatch trigger", self.file_name);
+        };
+
+        let mut payload = serde_json::Map::new();
+        for (key, value) in fields {
+            let Some(input) = inputs.iter().find(|input| &input.name == key) else {
+                let known: Vec<&str> = inputs.iter().map(|input| input.name.as_str()).collect();
+                if known.is_empty() {
+                    bail!("Workflow {} does not take any inputs", self.file_name);
+                }
+                bail!(
+                    "Unknown input '{}' for {}, expected one of: {}",
+                    key,
+                    self.file_name,
+                    known.join(", ")
+                );
+            };
+            input.validate(value)?;
+            payload.insert(key.clone(), serde_json::Value::String(value.clone()));
+        }
+
+        for input in inputs {
+            if input.required && input.default.is_none() && !payload.contains_key(&input.name) {
+                bail!(
+                    "Missing required input '{}' (pass it with -f {}=<value>)",
+                    input.name,
+                    input.name
+                );
+            }
+        }
+
+        Ok(serde_json::Value::Object(payload))
+    }
+}
+
+impl DispatchInput {
+    fn validate(&self, value: &str) -> Result<()> {
+        match self.input_type.as_str() {
+            "boolean" if value != "true" && value != "false" => {
+                bail!("Input '{}' must be true or false, got '{}'", self.name, value)
+            }
+            "number" if value.parse::<f64>().is_err() => {
+                bail!("Input '{}' must be a number, got '{}'", self.name, value)
+            }
+            "choice" if !self.options.iter().any(|option| option == value) => bail!(
+                "Input '{}' must be one of: {}, got '{}'",
+                self.name,
+                self.options.join(", "),
+                value
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Inputs of the `workflow_dispatch` trigger in a workflow's `on:` section
+/// `on` may be a single event, a list of events or a map of events to their settings
+fn dispatch_inputs(on: &Value) -> Option<Vec<DispatchInput>> {
+    match on {
+        Value::String(event) => (event == "workflow_dispatch").then(Vec::new),
+        Value::Sequence(events) => events
+            .iter()
+            .any(|event| event.as_str() == Some("workflow_dispatch"))
+            .then(Vec::new),
+        Value::Mapping(events) => {
+            let dispatch = events.get("workflow_dispatch")?;
+            let Some(inputs) = dispatch.get("inputs").and_then(Value::as_mapping) else {
+                return Some(Vec::new());
+            };
+            Some(
+                inputs
+                    .iter()
+                    .filter_map(|(name, spec)| {
+                        Some(DispatchInput {
+                            name: name.as_str()?.to_string(),
+                            description: spec
+                                .get("description")
+                                .and_then(Value::as_str)
+                                .map(str::to_string),
+                            required: spec.get("required").and_then(Value::as_bool).unwrap_or(false),
+                            default: spec.get("default").and_then(scalar_string),
+                            input_type: spec
+                                .get("type")
+                                .and_then(Value::as_str)
+                                .unwrap_or("string")
+                                .to_string(),
+                            options: spec
+                                .get("options")
+                                .and_then(Value::as_sequence)
+                                .map(|options| options.iter().filter_map(scalar_string).collect())
+                                .unwrap_or_default(),
+                        })
+                    })
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+fn scalar_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Load the workflows in `<repo_root>/.github/workflows`, sorted by file name
+/// Files that fail to parse are skipped
+pub fn load_workflows(repo_root: &Path) -> Vec<WorkflowFile> {
+    let Ok(entries) = std::fs::read_dir(repo_root.join(".github").join("workflows")) else {
+        return Vec::new();
+    };
+
+    let mut workflows: Vec<WorkflowFile> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().into_string().ok()?;
+            if !(file_name.ends_with(".yml") || file_name.ends_with(".yaml")) {
+                return None;
+            }
+            let yaml = std::fs::read_to_string(entry.path()).ok()?;
+            WorkflowFile::parse(&file_name, &yaml).ok()
+        })
+        .collect();
+
+    workflows.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    workflows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPLOY: &str = r#"
+name: Deploy
+on:
+  push:
+    branches: [main]
+  workflow_dispatch:
+    inputs:
+      environment:
+        description: Where to deploy
+        required: true
+        type: choice
+        options: [staging, production]
+      dry_run:
+        type: boolean
+        default: false
+      replicas:
+        type: number
+        required: true
+        default: 2
+"#;
+
+    fn fields(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_dispatch_inputs() {
+        let workflow = WorkflowFile::parse("deploy.yml", DEPLOY).unwrap();
+        let inputs = workflow.dispatch_inputs.unwrap();
+
+        assert_eq!(workflow.name.as_deref(), Some("Deploy"));
+        assert_eq!(inputs.len(), 3);
+        assert_eq!(inputs[0].name, "environment");
+        assert!(inputs[0].required);
+        assert_eq!(inputs[0].options, vec!["staging", "production"]);
+        assert_eq!(inputs[1].input_type, "boolean");
+        assert_eq!(inputs[1].default.as_deref(), Some("false"));
+        assert_eq!(inputs[2].default.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_dispatch_trigger_forms() {
+        let short = WorkflowFile::parse("a.yml", "on: workflow_dispatch").unwrap();
+        assert_eq!(short.dispatch_inputs, Some(vec![]));
+
+        let list = WorkflowFile::parse("b.yml", "on: [push, workflow_dispatch]").unwrap();
+        assert_eq!(list.dispatch_inputs, Some(vec![]));
+
+        let push_only = WorkflowFile::parse("c.yml", "on:\n  push:\n").unwrap();
+        assert_eq!(push_only.dispatch_inputs, None);
+    }
+
+    #[test]
+    fn test_validate_inputs() {
+        let workflow = WorkflowFile::parse("deploy.yml", DEPLOY).unwrap();
+
+        let payload = workflow
+            .validate_inputs(&fields(&[("environment", "staging"), ("dry_run", "true")]))
+            .unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({"environment": "staging", "dry_run": "true"})
+        );
+
+        assert!(workflow.validate_inputs(&fields(&[])).is_err());
+        assert!(workflow
+            .validate_inputs(&fields(&[("environment", "qa")]))
+            .is_err());
+        assert!(workflow
+            .validate_inputs(&fields(&[("environment", "staging"), ("dry_run", "yes")]))
+            .is_err());
+        assert!(workflow
+            .validate_inputs(&fields(&[("environment", "staging"), ("region", "eu")]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_matches() {
+        let workflow = WorkflowFile::parse("deploy.yml", DEPLOY).unwrap();
+        assert!(workflow.matches("deploy.yml"));
+        assert!(workflow.matches("deploy"));
+        assert!(workflow.matches("DEPLOY"));
+        assert!(!workflow.matches("ci"));
+    }
+}
@@ -7,11 +7,19 @@ mod tui;
 
 use clap::CommandFactory;
 use clap_complete::Shell;
-use std::io;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+use std::time::Duration;
 
-use application::{refresh_cache, watch_action};
+use application::{
+    action_logs, actions_stats, cancel_action, check_cache, dispatch_workflow, download_artifacts,
+    export_data, format_size, import_data, list_actions, list_artifacts, notify, refresh_cache,
+    render_run_table, repair_cache, rerun_action, watch_action, watch_checks, write_export,
+    CheckResult, ChecksOptions, Completion, DispatchOptions, ExportFilter, LogOptions,
+    NotifyOptions, RunQuery, StatsOptions, WatchOptions, WATCH_ERROR_EXIT_CODE,
+};
 use config::{parse_args, Commands};
-use infrastructure::{cache_path, Cache};
+use infrastructure::{cache_path, load_settings, Cache, CacheStats};
 use tui::matcher::RepoMatcher;
 
 #[tokio::main]
@@ -22,60 +30,115 @@ async fn main() -> anyhow::Result<()> {
     let token = get_token(&cli)?;
 
     // Default to Tui if no subcommand provided
-    match cli.command.unwrap_or(Commands::Tui) {
-        Commands::Tui => {
-            let cache = Cache::open()?;
-            tui::run(cache)?;
+    match cli.command.unwrap_or(Commands::Tui(cli.tui)) {
+        Commands::Tui(args) => {
+            let settings = load_settings(cli.config.as_deref())?;
+            let cache = open_cache(cli.cache_path.as_deref())?;
+            let ttl = Duration::from_secs(cli.refresh_ttl.saturating_mul(60 * 60));
+            let selected = tui::run(cache, token, ttl, &settings, &args)?;
+            // Let scripts tell a cancelled `--print` from an empty selection
+            if args.print.is_some() && !selected {
+                std::process::exit(1);
+            }
         }
 
         Commands::Data { action } => match action {
             config::DataCommands::Refresh => {
-                let result = refresh_cache(token, cli.quiet).await?;
+                let cache = open_cache(cli.cache_path.as_deref())?;
+                let result = refresh_cache(token, &cache, cli.quiet).await?;
                 if !cli.quiet {
                     println!("{}", result);
                 }
             }
             config::DataCommands::Clear => {
-                let cache = Cache::open()?;
+                let cache = open_cache(cli.cache_path.as_deref())?;
                 cache.clear()?;
                 if !cli.quiet {
                     println!("Cache cleared");
                 }
             }
             config::DataCommands::Status => {
-                let cache = Cache::open()?;
+                let cache = open_cache(cli.cache_path.as_deref())?;
                 let stats = cache.stats()?;
-                if !cli.quiet {
-                    println!("Cache Statistics:");
-                    println!("  Organizations: {}", stats.org_count);
-                    println!("  Repositories: {}", stats.repo_count);
-                    println!("  Size: {} bytes", stats.size_bytes);
-                } else {
+                if cli.quiet {
                     // JSON output for quiet mode (script-friendly)
-                    println!(
-                        "{{\"orgs\":{},\"repos\":{},\"size\":{}}}",
-                        stats.org_count, stats.repo_count, stats.size_bytes
-                    );
+                    println!("{}", serde_json::to_string(&stats)?);
+                } else {
+                    print_cache_stats(&stats);
                 }
             }
-            config::DataCommands::Export => {
-                let cache = Cache::open()?;
-                let orgs = cache.load_orgs()?;
-                let repos = cache.load_repos()?;
-
-                #[derive(serde::Serialize)]
-                struct ExportData {
-                    orgs: Vec<domain::Org>,
-                    repos: Vec<domain::Repo>,
+            config::DataCommands::Export {
+                format,
+                org,
+                language,
+                private,
+                query,
+            } => {
+                let filter = ExportFilter {
+                    org,
+                    language,
+                    private,
+                    query,
+                };
+                let cache = open_cache(cli.cache_path.as_deref())?;
+                let data = export_data(&cache, &filter)?;
+                write_export(&data, format, &mut io::stdout().lock())?;
+            }
+            config::DataCommands::Import { file, mode } => {
+                let cache = open_cache(cli.cache_path.as_deref())?;
+                let result = import_data(&cache, &file, mode)?;
+                if !cli.quiet {
+                    println!("{}", result);
                 }
-
-                let data = ExportData { orgs, repos };
-                println!("{}", serde_json::to_string_pretty(&data)?);
             }
             config::DataCommands::Reveal => {
-                let path = cache_path()?;
+                let path = cache_path(cli.cache_path.as_deref())?;
                 println!("{}", path.display());
             }
+            config::DataCommands::Vacuum => {
+                let cache = open_cache(cli.cache_path.as_deref())?;
+                let before = cache.stats()?.size_bytes;
+                cache.vacuum()?;
+                let after = cache.stats()?.size_bytes;
+                if !cli.quiet {
+                    println!("Vacuumed cache: {} bytes -> {} bytes", before, after);
+                }
+            }
+            config::DataCommands::Check { repair } => {
+                let path = cache_path(cli.cache_path.as_deref())?;
+                let check = check_cache(&path);
+                if matches!(check, CheckResult::Healthy) {
+                    if !cli.quiet {
+                        println!("{}", check);
+                    }
+                } else {
+                    eprintln!("{}", check);
+                    if !(repair || confirm("Repair the cache (rebuilding from GitHub if needed)?")) {
+                        anyhow::bail!("Cache is corrupt. Re-run with --repair to fix it");
+                    }
+                    let result = repair_cache(&path, token, cli.quiet).await?;
+                    if !cli.quiet {
+                        println!("{}", result);
+                    }
+                }
+            }
+            config::DataCommands::Prune { older_than } => {
+                let cache = open_cache(cli.cache_path.as_deref())?;
+                let cutoff = chrono::Duration::from_std(older_than)
+                    .ok()
+                    .and_then(|age| chrono::Utc::now().checked_sub_signed(age))
+                    .ok_or_else(|| anyhow::anyhow!("--older-than is too large"))?;
+                let stats = cache.prune(cutoff)?;
+                if !cli.quiet {
+                    if !stats.repos_checked {
+                        println!("No refresh since the cutoff, so no repos were removed (run `gg data refresh`)");
+                    }
+                    println!(
+                        "Removed {} repo(s) and {} cached workflow run(s), reset usage of {} repo(s)",
+                        stats.repos_removed, stats.runs_removed, stats.usage_reset
+                    );
+                }
+            }
         },
 
         Commands::Issues => {
@@ -87,7 +150,7 @@ async fn main() -> anyhow::Result<()> {
             }
         }
 
-        Commands::Actions => {
+        Commands::Actions { action: None } => {
             let repo = git::get_github_repo()?;
             let url = repo.url_for("actions");
             open::that(&url)?;
@@ -96,6 +159,129 @@ async fn main() -> anyhow::Result<()> {
             }
         }
 
+        Commands::Actions {
+            action: Some(config::ActionsCommands::Dashboard { count, interval }),
+        } => {
+            let cache = open_cache(cli.cache_path.as_deref())?;
+            tui::run_dashboard(cache, token, count, Duration::from_secs(interval.max(5)))?;
+        }
+
+        Commands::Actions {
+            action:
+                Some(config::ActionsCommands::Stats {
+                    workflow,
+                    days,
+                    refresh,
+                    json,
+                }),
+        } => {
+            let cache = open_cache(cli.cache_path.as_deref())?;
+            let options = StatsOptions {
+                workflow,
+                days: days.max(1),
+                refresh,
+            };
+            let stats = actions_stats(token, &cache, &options, cli.quiet || json).await?;
+            if json {
+                println!("{}", serde_json::to_string(&stats)?);
+            } else {
+                print!("{}", stats);
+            }
+        }
+
+        Commands::Actions {
+            action: Some(config::ActionsCommands::Logs { run, full, job }),
+        } => {
+            let options = LogOptions {
+                run: run_query(run),
+                full,
+                job,
+            };
+            let logs = action_logs(token, &options).await?;
+            if logs.sections.is_empty() {
+                if !cli.quiet {
+                    println!("No failed jobs in {} #{} ({})", logs.run.name, logs.run.id, logs.run.html_url);
+                }
+            } else {
+                for section in &logs.sections {
+                    println!("{}", section);
+                }
+            }
+        }
+
+        Commands::Actions {
+            action: Some(config::ActionsCommands::Artifacts { run }),
+        } => {
+            let result = list_artifacts(token, &run_query(run)).await?;
+            if !cli.quiet {
+                println!("{} #{} · {}", result.run.name, result.run.id, result.run.head_branch);
+            }
+            if result.artifacts.is_empty() {
+                if !cli.quiet {
+                    println!("No artifacts");
+                }
+            } else {
+                let width = result.artifacts.iter().map(|a| a.name.len()).max().unwrap_or(0);
+                for artifact in &result.artifacts {
+                    let expires = match artifact.expires_at {
+                        _ if artifact.expired => "expired".to_string(),
+                        Some(at) => format!("expires {}", at.format("%Y-%m-%d")),
+                        None => String::new(),
+                    };
+                    println!(
+                        "{:<width$}  {:>9}  {}",
+                        artifact.name,
+                        format_size(artifact.size_in_bytes),
+                        expires
+                    );
+                }
+            }
+        }
+
+        Commands::Actions {
+            action: Some(config::ActionsCommands::Download { name, dir, run }),
+        } => {
+            let downloaded = download_artifacts(token, &run_query(run), name.as_deref(), &dir).await?;
+            if !cli.quiet {
+                for artifact in &downloaded {
+                    println!("{}", artifact);
+                }
+            }
+        }
+
+        Commands::Actions {
+            action: Some(config::ActionsCommands::Rerun { run, failed }),
+        } => {
+            let run = rerun_action(token, &run_query(run), failed).await?;
+            if !cli.quiet {
+                let jobs = if failed { "failed jobs of " } else { "" };
+                println!("Re-running {}{} #{} ({})", jobs, run.name, run.id, run.html_url);
+            }
+        }
+
+        Commands::Actions {
+            action: Some(config::ActionsCommands::Cancel { run }),
+        } => {
+            let run = cancel_action(token, &run_query(run)).await?;
+            if !cli.quiet {
+                println!("Cancelling {} #{} ({})", run.name, run.id, run.html_url);
+            }
+        }
+
+        Commands::Actions {
+            action: Some(config::ActionsCommands::Dispatch { workflow, git_ref, fields }),
+        } => {
+            let options = DispatchOptions {
+                workflow,
+                git_ref,
+                fields,
+            };
+            let result = dispatch_workflow(token, &options).await?;
+            if !cli.quiet {
+                println!("{}", result);
+            }
+        }
+
         Commands::Settings => {
             let repo = git::get_github_repo()?;
             let url = repo.url_for("settings");
@@ -124,24 +310,87 @@ async fn main() -> anyhow::Result<()> {
         }
 
         Commands::Watch { target } => match target {
-            config::WatchCommands::Action => {
-                let result = watch_action(token, cli.quiet).await?;
+            config::WatchCommands::Action {
+                run,
+                list: true,
+                limit,
+                ..
+            } => {
+                let runs = list_actions(token, &run_query(run), limit).await?;
+                if runs.is_empty() {
+                    if !cli.quiet {
+                        println!("No matching workflow runs");
+                    }
+                } else {
+                    for line in render_run_table(&runs, io::stdout().is_terminal()) {
+                        println!("{}", line);
+                    }
+                }
+            }
+            config::WatchCommands::Action {
+                run,
+                open,
+                interval,
+                notify: notify_args,
+                ..
+            } => {
+                let options = WatchOptions {
+                    run: run_query(run),
+                    open_only: open,
+                    interval: Duration::from_secs(interval.max(1)),
+                };
+                // Keep errors apart from a failed run, for `gg watch action && deploy`
+                let result = match watch_action(token, &options, cli.quiet).await {
+                    Ok(result) => result,
+                    Err(e) => {
+                        eprintln!("Error: {:?}", e);
+                        std::process::exit(WATCH_ERROR_EXIT_CODE);
+                    }
+                };
+                if open {
+                    if !cli.quiet {
+                        println!("Opening: {}", result);
+                    }
+                    open::that(&result.url)?;
+                } else {
+                    if !cli.quiet {
+                        println!("{}", result);
+                    }
+                    for e in notify(&notify_options(notify_args), &Completion::from(&result)).await {
+                        eprintln!("Notification failed: {:#}", e);
+                    }
+                    std::process::exit(result.exit_code());
+                }
+            }
+            config::WatchCommands::Checks {
+                interval,
+                timeout,
+                notify: notify_args,
+            } => {
+                let options = ChecksOptions {
+                    interval: Duration::from_secs(interval.max(1)),
+                    timeout: Duration::from_secs(timeout.saturating_mul(60)),
+                };
+                let result = watch_checks(token, &options, cli.quiet).await?;
                 if !cli.quiet {
-                    println!("Opening: {}", result);
+                    println!("{}", result);
                 }
-                open::that(&result.url)?;
+                for e in notify(&notify_options(notify_args), &Completion::from(&result)).await {
+                    eprintln!("Notification failed: {:#}", e);
+                }
+                std::process::exit(result.exit_code());
             }
         },
 
         Commands::Raycast { action } => match action {
             config::RaycastCommands::Search { query, count, json } => {
-                let cache = Cache::open()?;
+                let cache = open_cache(cli.cache_path.as_deref())?;
                 let repos = cache.load_repos()?;
                 let orgs = cache.load_orgs()?;
 
                 let mut matcher = RepoMatcher::new(repos, orgs);
                 matcher.update_pattern(query);
-                matcher.tick();
+                matcher.finish();
 
                 let results: Vec<String> = matcher
                     .matches_sorted()
@@ -178,9 +427,102 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+fn print_cache_stats(stats: &CacheStats) {
+    let format_time = |at: Option<chrono::DateTime<chrono::Utc>>| {
+        at.map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "never".to_string())
+    };
+
+    println!("Cache Statistics:");
+    println!("  Organizations: {}", stats.org_count);
+    println!(
+        "  Repositories: {} ({} private, {} public)",
+        stats.repo_count, stats.private_count, stats.public_count
+    );
+    println!("  Size: {} bytes", stats.size_bytes);
+    println!("  Schema version: {}", stats.schema_version);
+    println!("  Last refresh: {}", format_time(stats.last_refresh_at));
+
+    if !stats.orgs.is_empty() {
+        println!();
+        println!("Repos by owner:");
+        for org in &stats.orgs {
+            println!(
+                "  {:<30} {:>5}  refreshed {}",
+                org.login,
+                org.repo_count,
+                format_time(org.last_refresh_at)
+            );
+        }
+    }
+
+    if !stats.languages.is_empty() {
+        println!();
+        println!("Languages:");
+        for language in &stats.languages {
+            println!("  {:<30} {:>5}", language.language, language.repo_count);
+        }
+    }
+
+    if !stats.top_repos.is_empty() {
+        println!();
+        println!("Most used:");
+        for repo in &stats.top_repos {
+            println!(
+                "  {:<40} {:>4} opens, last {}",
+                repo.full_name,
+                repo.access_count,
+                format_time(repo.last_accessed_at)
+            );
+        }
+    }
+}
+
 fn get_token(cli: &config::Cli) -> anyhow::Result<String> {
     cli.token
         .clone()
         .or_else(|| std::env::var("GITHUB_TOKEN").ok())
         .ok_or_else(|| anyhow::anyhow!("GitHub token required. Set GITHUB_TOKEN env var or use --token flag"))
 }
+
+fn open_cache(path: Option<&Path>) -> anyhow::Result<Cache> {
+    Cache::open(&cache_path(path)?)
+}
+
+/// Build the run query for the run selection arguments shared by the actions commands
+fn run_query(args: config::RunArgs) -> RunQuery {
+    RunQuery {
+        run_id: args.run,
+        workflow: args.workflow,
+        branch: args.branch,
+        event: args.event,
+        head_sha: args.head_sha,
+        status: args.status,
+    }
+}
+
+/// Build the notification sinks from the watch command arguments
+fn notify_options(args: config::NotifyArgs) -> NotifyOptions {
+    NotifyOptions {
+        bell: args.bell,
+        terminal: args.notify,
+        command: args.on_complete,
+        webhook: args.webhook,
+    }
+}
+
+/// Ask a yes/no question on the terminal, defaulting to no when stdin is not interactive
+fn confirm(question: &str) -> bool {
+    if !io::stdin().is_terminal() {
+        return false;
+    }
+
+    eprint!("{} [y/N] ", question);
+    let _ = io::stderr().flush();
+
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
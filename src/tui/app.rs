@@ -1,17 +1,78 @@
-use super::matcher::RepoMatcher;
+use super::input::LineEditor;
+use super::keymap::{applies_to, expand_template, Command, KeyAction, KeyMap};
+use super::matcher::{RepoItem, RepoMatcher};
+use super::menu::{ActionMenu, MenuOutcome, DEFAULT_CLONE_PATH};
 use super::ui;
+use crate::application::{
+    needs_refresh, shell_command, shell_quote, spawn_background_refresh, RefreshEvent,
+};
+use crate::config::TuiArgs;
 use crate::domain::{Org, Repo};
-use crate::infrastructure::Cache;
-use anyhow::Result;
+use crate::infrastructure::{BindingTarget, Cache, Settings};
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent},
+    event::{self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io::{self, IsTerminal};
+use std::fs::{File, OpenOptions};
+use std::io::{self, IsTerminal, Write};
+use std::sync::mpsc::{Receiver, TryRecvError};
 use std::time::Duration;
 
+/// Progress of a background refresh started by the TUI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefreshStatus {
+    /// Still fetching; `repos` counts repos received so far
+    Running { repos: usize },
+    /// Finished and saved to the cache
+    Finished { repos: usize },
+    /// Stopped with an error
+    Failed(String),
+}
+
+/// An accepted item, expanded through a template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    /// URL to open or text to print
+    pub text: String,
+    /// Repo to record the access for, orgs don't keep usage stats
+    pub full_name: Option<String>,
+}
+
+impl Selection {
+    fn new(template: &str, item: &RepoItem) -> Self {
+        Self {
+            text: expand_template(template, item, str::to_string),
+            full_name: (!item.is_org()).then(|| item.full_name.clone()),
+        }
+    }
+}
+
+/// What a key press asks the caller to do, for each marked item (or the selected one)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Leave the TUI and open each URL
+    Open(Vec<Selection>),
+    /// Leave the TUI and write each text to stdout, one per line
+    Print(Vec<Selection>),
+    /// Copy the texts to the clipboard, one per line, and keep going
+    Copy(Vec<String>),
+    /// Leave the TUI and run each shell command in turn
+    Run(Vec<String>),
+}
+
+/// An org the list has been drilled down into
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Scope {
+    /// Org login the list is limited to
+    org: String,
+    /// Input pattern before drilling down, restored when popping back out
+    parent_pattern: String,
+}
+
 /// Main TUI application state
 pub struct App {
     /// Matcher for fuzzy searching repos
@@ -19,7 +80,11 @@ pub struct App {
     /// Currently selected index in the match list
     selected_index: usize,
     /// User's input pattern for fuzzy matching
-    input_pattern: String,
+    input: LineEditor,
+    /// Rows moved by PageUp/PageDown, the height of the visible list
+    page_size: usize,
+    /// Org the list is scoped to, if drilled down
+    scope: Option<Scope>,
     /// Whether the app should exit
     should_exit: bool,
     /// Total number of orgs
@@ -28,6 +93,22 @@ pub struct App {
     total_repos: usize,
     /// Whether help is currently shown
     show_help: bool,
+    /// Whether the preview pane is shown (when the terminal is wide enough)
+    show_preview: bool,
+    /// Background refresh progress, if one was started
+    refresh_status: Option<RefreshStatus>,
+    /// Key bindings, built-in and from the settings file
+    keymap: KeyMap,
+    /// Message for the status bar, cleared by the next key press
+    notice: Option<String>,
+    /// Template printing the accepted item instead of opening it, from `--print`
+    print: Option<String>,
+    /// Items marked with Tab, in the order they were marked
+    marked: Vec<RepoItem>,
+    /// Action menu for the selected item, when popped up
+    menu: Option<ActionMenu>,
+    /// Where the action menu clones repos
+    clone_path: String,
 }
 
 impl App {
@@ -40,42 +121,209 @@ impl App {
         Self {
             matcher,
             selected_index: 0,
-            input_pattern: String::new(),
+            input: LineEditor::default(),
+            page_size: 10,
+            scope: None,
             should_exit: false,
             total_orgs,
             total_repos,
             show_help: false,
+            show_preview: true,
+            refresh_status: None,
+            keymap: KeyMap::default(),
+            notice: None,
+            print: None,
+            marked: Vec::new(),
+            menu: None,
+            clone_path: DEFAULT_CLONE_PATH.to_string(),
         }
     }
 
+    /// Print accepted items with `template` instead of opening them
+    /// A bare field name such as `url` is taken as `{url}`
+    pub fn with_print(mut self, template: Option<String>) -> Self {
+        self.print = template.map(|template| {
+            if template.contains('{') {
+                template
+            } else {
+                format!("{{{}}}", template)
+            }
+        });
+        self
+    }
+
+    /// Use `keymap` instead of the built-in key bindings
+    pub fn with_keymap(mut self, keymap: KeyMap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// Clone repos from the action menu to `clone_path`, a template like `~/src/{name}`
+    pub fn with_clone_path(mut self, clone_path: String) -> Self {
+        self.clone_path = clone_path;
+        self
+    }
+
+    /// Get the key bindings
+    pub fn keymap(&self) -> &KeyMap {
+        &self.keymap
+    }
+
+    /// Get the status bar message
+    pub fn notice(&self) -> Option<&str> {
+        self.notice.as_deref()
+    }
+
+    /// Show a message in the status bar until the next key press
+    pub fn set_notice(&mut self, notice: String) {
+        self.notice = Some(notice);
+    }
+
     /// Get the current sorted matches
-    pub fn matches(&self) -> Vec<&super::matcher::RepoItem> {
+    pub fn matches(&self) -> Vec<&RepoItem> {
         self.matcher.matches_sorted()
     }
 
+    /// Positions of the characters of `item` matched by the input pattern
+    pub fn match_indices(&self, item: &RepoItem) -> Vec<u32> {
+        self.matcher.match_indices(item)
+    }
+
     /// Get the match count
     pub fn match_count(&self) -> usize {
         self.matcher.match_count()
     }
 
     /// Get the currently selected item
-    pub fn selected_item(&self) -> Option<&super::matcher::RepoItem> {
+    pub fn selected_item(&self) -> Option<&RepoItem> {
         let matches = self.matches();
         matches.get(self.selected_index).copied()
     }
 
-    /// Handle a character input (add to pattern)
+    /// Replace the query, as with `--query`
+    pub fn set_query(&mut self, query: String) {
+        self.edit(|input| input.set(query));
+    }
+
+    /// What accepting does: open the marked or selected items, or print them with `--print`
+    pub fn accept(&self) -> Option<Outcome> {
+        let targets = self.targets();
+        if targets.is_empty() {
+            return None;
+        }
+        Some(match &self.print {
+            Some(template) => Outcome::Print(targets.iter().map(|item| Selection::new(template, item)).collect()),
+            None => Outcome::Open(targets.iter().map(|item| Selection::new("{url}", item)).collect()),
+        })
+    }
+
+    /// Whether `item` is marked
+    pub fn is_marked(&self, item: &RepoItem) -> bool {
+        self.marked.iter().any(|marked| marked.full_name == item.full_name)
+    }
+
+    /// Number of marked items
+    pub fn marked_count(&self) -> usize {
+        self.marked.len()
+    }
+
+    /// Mark the selected item, or unmark it if it already is
+    pub fn toggle_mark(&mut self) {
+        let Some(item) = self.selected_item().cloned() else {
+            return;
+        };
+        match self.marked.iter().position(|marked| marked.full_name == item.full_name) {
+            Some(index) => {
+                self.marked.remove(index);
+            }
+            None => self.marked.push(item),
+        }
+    }
+
+    /// Pop up the action menu for the selected item
+    pub fn open_menu(&mut self) {
+        if let Some(item) = self.selected_item() {
+            self.menu = Some(ActionMenu::new(item, &self.keymap, &self.clone_path));
+        }
+    }
+
+    /// Get the action menu, if popped up
+    pub fn menu(&self) -> Option<&ActionMenu> {
+        self.menu.as_ref()
+    }
+
+    /// Items actions apply to, the marked ones or else the selected one
+    fn targets(&self) -> Vec<&RepoItem> {
+        if self.marked.is_empty() {
+            self.selected_item().into_iter().collect()
+        } else {
+            self.marked.iter().collect()
+        }
+    }
+
+    /// Handle a character input (insert at the cursor)
     pub fn on_char(&mut self, c: char) {
-        self.input_pattern.push(c);
-        self.matcher.update_pattern(self.input_pattern.clone());
-        self.selected_index = 0; // Reset selection when pattern changes
+        self.edit(|input| input.insert(c));
     }
 
-    /// Handle backspace (remove last character from pattern)
+    /// Handle pasted text (insert at the cursor)
+    pub fn on_paste(&mut self, text: &str) {
+        self.edit(|input| input.insert_str(text));
+    }
+
+    /// Handle backspace (remove the character before the cursor)
+    /// On an empty pattern this pops back out of an org
     pub fn on_backspace(&mut self) {
-        self.input_pattern.pop();
-        self.matcher.update_pattern(self.input_pattern.clone());
-        self.selected_index = 0;
+        if self.input.is_empty() {
+            self.leave_org();
+            return;
+        }
+        self.edit(LineEditor::backspace);
+    }
+
+    /// Apply an edit to the input, rematching if the text changed
+    fn edit(&mut self, f: impl FnOnce(&mut LineEditor)) {
+        let before = self.input.text().to_string();
+        f(&mut self.input);
+        if self.input.text() != before {
+            self.update_matcher();
+        }
+    }
+
+    /// Pass the pattern on to the matcher, limited to the scoped org's repos
+    fn update_matcher(&mut self) {
+        let pattern = match &self.scope {
+            Some(scope) => format!("org:{} -is:org {}", scope.org, self.input.text()),
+            None => self.input.text().to_string(),
+        };
+        self.matcher.update_pattern(pattern);
+        self.selected_index = 0; // Reset selection when pattern changes
+    }
+
+    /// Drill down into the selected org, listing only its repos
+    pub fn enter_org(&mut self) {
+        let Some(item) = self.selected_item().filter(|item| item.is_org()) else {
+            return;
+        };
+        self.scope = Some(Scope {
+            org: item.repo.owner_login.clone(),
+            parent_pattern: self.input.take(),
+        });
+        self.update_matcher();
+    }
+
+    /// Pop back out of the scoped org, restoring the pattern from before
+    pub fn leave_org(&mut self) {
+        let Some(scope) = self.scope.take() else {
+            return;
+        };
+        self.input.set(scope.parent_pattern);
+        self.update_matcher();
+    }
+
+    /// Org the list is scoped to, if drilled down
+    pub fn scope(&self) -> Option<&str> {
+        self.scope.as_ref().map(|scope| scope.org.as_str())
     }
 
     /// Handle moving up in the list
@@ -88,15 +336,20 @@ impl App {
 
     /// Handle moving down in the list
     pub fn on_down(&mut self) {
+        self.move_down(1);
+    }
+
+    /// Move the selection down by `rows`, stopping at the last match
+    fn move_down(&mut self, rows: usize) {
         let count = self.match_count();
         if count > 0 {
-            self.selected_index = (self.selected_index + 1).min(count - 1);
+            self.selected_index = (self.selected_index + rows).min(count - 1);
         }
     }
 
-    /// Handle Enter key - return the URL of the selected item
-    pub fn on_enter(&mut self) -> Option<String> {
-        self.selected_item().map(|item| item.url.clone())
+    /// Set how many rows PageUp/PageDown move
+    pub fn set_page_size(&mut self, rows: usize) {
+        self.page_size = rows.max(1);
     }
 
     /// Handle exit keys (Esc, Ctrl+C)
@@ -111,7 +364,12 @@ impl App {
 
     /// Get the input pattern
     pub fn input_pattern(&self) -> &str {
-        &self.input_pattern
+        self.input.text()
+    }
+
+    /// Get the cursor position in the input, in characters
+    pub fn input_cursor(&self) -> usize {
+        self.input.cursor()
     }
 
     /// Get the selected index
@@ -139,104 +397,317 @@ impl App {
         self.show_help = !self.show_help;
     }
 
+    /// Check if the preview pane is enabled
+    pub fn show_preview(&self) -> bool {
+        self.show_preview
+    }
+
+    /// Toggle the preview pane
+    pub fn toggle_preview(&mut self) {
+        self.show_preview = !self.show_preview;
+    }
+
+    /// Get the background refresh progress
+    pub fn refresh_status(&self) -> Option<&RefreshStatus> {
+        self.refresh_status.as_ref()
+    }
+
+    /// Apply a background refresh event, streaming new items into the matcher
+    pub fn on_refresh_event(&mut self, event: RefreshEvent) {
+        let received = match &self.refresh_status {
+            Some(RefreshStatus::Running { repos }) => *repos,
+            _ => 0,
+        };
+
+        self.refresh_status = Some(match event {
+            RefreshEvent::Orgs(orgs) => {
+                self.total_orgs += self.matcher.push_orgs(orgs);
+                RefreshStatus::Running { repos: received }
+            }
+            RefreshEvent::Repos(repos) => {
+                let count = repos.len();
+                self.total_repos += self.matcher.push_repos(repos);
+                RefreshStatus::Running {
+                    repos: received + count,
+                }
+            }
+            RefreshEvent::Finished => RefreshStatus::Finished { repos: received },
+            RefreshEvent::Failed(message) => RefreshStatus::Failed(message),
+        });
+    }
+
     /// Tick the matcher (process pending pattern changes)
     pub fn tick(&mut self) {
         self.matcher.tick();
     }
 
-    /// Handle a key event
-    pub fn handle_key_event(&mut self, key: KeyEvent) -> Option<String> {
-        match key.code {
-            KeyCode::Char(c) => {
-                // Check for Ctrl+key combinations
-                if key
-                    .modifiers
-                    .contains(crossterm::event::KeyModifiers::CONTROL)
-                {
-                    return self.on_ctrl_key(c);
+    /// Tick the matcher until the matches are complete
+    pub fn finish_matching(&mut self) {
+        self.matcher.finish();
+    }
+
+    /// Handle a key event through the key map
+    /// Unbound characters are typed into the query
+    pub fn handle_key_event(&mut self, key: KeyEvent) -> Option<Outcome> {
+        self.notice = None;
+        if let Some(menu) = self.menu.as_mut() {
+            return match menu.handle_key(&key)? {
+                MenuOutcome::Close => {
+                    self.menu = None;
+                    None
                 }
-                // Handle ? key for help
-                if c == '?' {
-                    self.toggle_help();
-                    return None;
+                MenuOutcome::Choose(entry) => {
+                    self.menu = None;
+                    self.on_action(entry.target, &entry.action)
                 }
-                self.on_char(c);
-                None
+            };
+        }
+
+        // With items marked, repo or org bindings are picked by the first one
+        let is_org = self.targets().first().map(|item| item.is_org());
+        if let Some(binding) = self.keymap.lookup(&key, is_org).cloned() {
+            return self.on_action(binding.target, &binding.action);
+        }
+
+        if let KeyCode::Char(c) = key.code
+            && !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            self.on_char(c);
+        }
+        None
+    }
+
+    /// Perform an action from a key binding or the menu, returning what the caller should do with it
+    fn on_action(&mut self, target: BindingTarget, action: &KeyAction) -> Option<Outcome> {
+        // Item actions apply to the marked or selected items of the kind they are for
+        let targets: Vec<&RepoItem> = self
+            .targets()
+            .into_iter()
+            .filter(|item| applies_to(target, Some(item.is_org())))
+            .collect();
+
+        let outcome = match action {
+            KeyAction::Command(Command::Accept) => return self.accept(),
+            KeyAction::Command(command) => {
+                self.on_command(*command);
+                return None;
             }
-            KeyCode::Backspace => {
-                self.on_backspace();
-                None
+            KeyAction::Open(template) => {
+                Outcome::Open(targets.iter().map(|item| Selection::new(template, item)).collect())
             }
-            KeyCode::Up => {
-                self.on_up();
-                None
+            KeyAction::Copy(template) => Outcome::Copy(
+                targets
+                    .iter()
+                    .map(|item| expand_template(template, item, str::to_string))
+                    .collect(),
+            ),
+            KeyAction::Run(template) => Outcome::Run(
+                targets
+                    .iter()
+                    .map(|item| expand_template(template, item, shell_quote))
+                    .collect(),
+            ),
+        };
+        (!targets.is_empty()).then_some(outcome)
+    }
+
+    /// Run a built-in editing, navigation or UI command
+    fn on_command(&mut self, command: Command) {
+        match command {
+            Command::Up => self.on_up(),
+            Command::Down => self.on_down(),
+            Command::PageUp => self.selected_index = self.selected_index.saturating_sub(self.page_size),
+            Command::PageDown => self.move_down(self.page_size),
+            Command::First => self.selected_index = 0,
+            Command::Last => self.move_down(usize::MAX),
+            Command::Left => {
+                if self.input.is_empty() {
+                    self.leave_org();
+                } else {
+                    self.input.left();
+                }
             }
-            KeyCode::Down => {
-                self.on_down();
-                None
+            Command::Right => {
+                // At the end of the input → drills down into an org, or pops up a repo's menu
+                if !self.input.is_at_end() {
+                    self.input.right();
+                } else if self.selected_item().is_some_and(RepoItem::is_org) {
+                    self.enter_org();
+                } else {
+                    self.open_menu();
+                }
+            }
+            Command::LineStart => self.input.home(),
+            Command::LineEnd => self.input.end(),
+            Command::WordLeft => self.input.word_left(),
+            Command::WordRight => self.input.word_right(),
+            Command::Backspace => self.on_backspace(),
+            Command::Delete => self.edit(LineEditor::delete),
+            Command::DeleteWord => self.edit(LineEditor::delete_word),
+            Command::DeleteToStart => self.edit(LineEditor::delete_to_start),
+            // Handled by `on_action`, since it leaves the TUI
+            Command::Accept => {}
+            Command::MarkDown => {
+                // Tab on an org drills down into it rather than marking it
+                if self.selected_item().is_some_and(RepoItem::is_org) {
+                    self.enter_org();
+                } else {
+                    self.toggle_mark();
+                    self.on_down();
+                }
+            }
+            Command::MarkUp => {
+                self.toggle_mark();
+                self.on_up();
             }
-            KeyCode::Enter => self.on_enter(),
-            KeyCode::Esc => {
+            Command::ActionMenu => self.open_menu(),
+            Command::ToggleHelp => self.toggle_help(),
+            Command::TogglePreview => self.toggle_preview(),
+            Command::Escape => {
                 // Close help if open, otherwise exit
                 if self.show_help {
                     self.show_help = false;
-                    None
                 } else {
                     self.on_exit();
-                    None
                 }
             }
-            _ => None,
+            Command::Quit => self.on_exit(),
         }
     }
+}
 
-    /// Handle Ctrl+key combinations
-    fn on_ctrl_key(&mut self, c: char) -> Option<String> {
-        let Some(item) = self.selected_item() else {
-            return None;
-        };
+/// Background refresh in progress, collecting results to save to the cache
+struct BackgroundRefresh {
+    events: Receiver<RefreshEvent>,
+    orgs: Vec<Org>,
+    repos: Vec<Repo>,
+}
 
-        let base_url = &item.url;
-        let suffix = match c {
-            'a' => "/actions",
-            'i' => "/issues",
-            'p' => "/pulls",
-            'm' => "/milestones",
-            _ => return None,
-        };
+impl BackgroundRefresh {
+    fn start(token: String) -> Self {
+        Self {
+            events: spawn_background_refresh(token),
+            orgs: Vec::new(),
+            repos: Vec::new(),
+        }
+    }
+
+    /// Pass pending events on to the app, saving each batch to the cache as it arrives
+    /// so leaving the TUI early keeps what was fetched
+    /// Returns false when the refresh is over
+    fn poll(&mut self, app: &mut App, cache: &Cache) -> bool {
+        loop {
+            let event = match self.events.try_recv() {
+                Ok(RefreshEvent::Orgs(orgs)) => match cache.store_orgs(&orgs) {
+                    Ok(()) => {
+                        self.orgs.extend(orgs.iter().cloned());
+                        RefreshEvent::Orgs(orgs)
+                    }
+                    Err(e) => RefreshEvent::Failed(format!("Failed to save refresh: {:#}", e)),
+                },
+                Ok(RefreshEvent::Repos(repos)) => match cache.merge_repos(&repos) {
+                    Ok(()) => {
+                        self.repos.extend(repos.iter().cloned());
+                        RefreshEvent::Repos(repos)
+                    }
+                    Err(e) => RefreshEvent::Failed(format!("Failed to save refresh: {:#}", e)),
+                },
+                Ok(RefreshEvent::Finished) => match self.save(cache) {
+                    Ok(()) => RefreshEvent::Finished,
+                    Err(e) => RefreshEvent::Failed(format!("Failed to save refresh: {:#}", e)),
+                },
+                Ok(event) => event,
+                Err(TryRecvError::Empty) => return true,
+                Err(TryRecvError::Disconnected) => {
+                    RefreshEvent::Failed("Refresh stopped unexpectedly".to_string())
+                }
+            };
 
-        Some(format!("{}{}", base_url, suffix))
+            let done = matches!(event, RefreshEvent::Finished | RefreshEvent::Failed(_));
+            app.on_refresh_event(event);
+            if done {
+                return false;
+            }
+        }
+    }
+
+    /// Store the complete refresh, dropping repos of owners no longer seen
+    fn save(&self, cache: &Cache) -> Result<()> {
+        cache.store_orgs(&self.orgs)?;
+        cache.store_repos(&self.repos)?;
+        cache.set_last_refresh(Utc::now())
     }
 }
 
 /// Run the TUI application
-pub fn run(cache: Cache) -> Result<()> {
-    // Check if we're running in a terminal
-    if !io::stdout().is_terminal() {
-        anyhow::bail!(
-            "TUI requires a terminal (TTY). Please run this command in an interactive terminal."
-        );
-    }
+/// Starts a background refresh when the cache is empty or older than `refresh_ttl`
+/// Returns whether an item was accepted
+pub fn run(
+    cache: Cache,
+    token: String,
+    refresh_ttl: Duration,
+    settings: &Settings,
+    args: &TuiArgs,
+) -> Result<bool> {
+    let keymap = KeyMap::new(&settings.keybindings)?;
 
     // Load data from cache first (before touching terminal)
     let repos = cache.load_repos()?;
     let orgs = cache.load_orgs()?;
     eprintln!("Loaded {} repos, {} orgs", repos.len(), orgs.len());
 
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    let has_repos = !repos.is_empty();
+    let mut app = App::new(repos, orgs)
+        .with_keymap(keymap)
+        .with_print(args.print.clone());
+    if let Some(clone_path) = &settings.clone_path {
+        app = app.with_clone_path(clone_path.clone());
+    }
+    if let Some(query) = &args.query {
+        app.set_query(query.clone());
+    }
 
-    let backend = CrosstermBackend::new(stdout);
+    // --select-1 and --exit-0 settle things from the cache without drawing anything
+    if args.select_one || args.exit_zero {
+        app.finish_matching();
+        match app.match_count() {
+            0 if args.exit_zero => return Ok(false),
+            1 if args.select_one => return finish(app.accept(), &cache),
+            _ => {}
+        }
+    }
+
+    // When printing, the TUI is drawn on the terminal itself so stdout only gets the selection
+    let output: Box<dyn Write> = if args.print.is_some() {
+        Box::new(open_tty()?)
+    } else if io::stdout().is_terminal() {
+        Box::new(io::stdout())
+    } else {
+        bail!("TUI requires a terminal (TTY). Please run this command in an interactive terminal.");
+    };
+
+    let mut refresh = needs_refresh(cache.last_refresh()?, has_repos, refresh_ttl)
+        .then(|| BackgroundRefresh::start(token));
+
+    enable_raw_mode()?;
+    let mut backend = CrosstermBackend::new(output);
+    execute!(backend, EnterAlternateScreen, EnableBracketedPaste)?;
     let mut terminal = Terminal::new(backend)?;
 
     // Main event loop
-    let mut app = App::new(repos, orgs);
     let result = loop {
+        // Stream in any background refresh results
+        if let Some(bg) = refresh.as_mut()
+            && !bg.poll(&mut app, &cache)
+        {
+            refresh = None;
+        }
+
         // Tick the matcher
         app.tick();
 
-        // Render UI
+        // Render UI, paging by the height of the list
+        app.set_page_size(ui::list_height(terminal.size()?.height));
         terminal.draw(|f| ui::render(f, &app))?;
 
         // Check for exit
@@ -246,28 +717,31 @@ pub fn run(cache: Cache) -> Result<()> {
 
         // Poll for events (with timeout for matcher updates)
         match event::poll(Duration::from_millis(50)) {
-            Ok(true) => {
-                if let Event::Key(key) = event::read()? {
-                    // Handle Ctrl+C or Ctrl+d for exit
-                    if (key.code == KeyCode::Char('c') || key.code == KeyCode::Char('d'))
-                        && key
-                            .modifiers
-                            .contains(crossterm::event::KeyModifiers::CONTROL)
-                    {
-                        break None;
-                    }
-                    if let Some(url) = app.handle_key_event(key) {
-                        break Some(url);
+            Ok(true) => match event::read()? {
+                Event::Key(key) => match app.handle_key_event(key) {
+                    Some(Outcome::Copy(texts)) => {
+                        // OSC 52 asks the terminal to set the clipboard, which also works over SSH
+                        let backend = terminal.backend_mut();
+                        write!(backend, "{}", osc52(&texts.join("\n")))?;
+                        backend.flush()?;
+                        app.set_notice(match texts.as_slice() {
+                            [text] => format!("Copied {}", text),
+                            _ => format!("Copied {} items", texts.len()),
+                        });
                     }
-                }
-            }
+                    Some(outcome) => break Some(outcome),
+                    None => {}
+                },
+                Event::Paste(text) => app.on_paste(&text),
+                _ => {}
+            },
             Ok(false) => {
                 // No event, continue loop
             }
             Err(e) => {
                 // Restore terminal before returning error
                 let _ = disable_raw_mode();
-                let _ = execute!(terminal.backend_mut(), LeaveAlternateScreen);
+                let _ = execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableBracketedPaste);
                 return Err(e.into());
             }
         }
@@ -275,16 +749,396 @@ pub fn run(cache: Cache) -> Result<()> {
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-
-    // Open URL in browser if selected
-    if let Some(url) = result {
-        eprintln!("Opening: {}", url);
-        open::that(&url)?;
-        // Record access in cache
-        let full_name = url.strip_prefix("https://github.com/").unwrap_or(&url);
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableBracketedPaste)?;
+
+    finish(result, &cache)
+}
+
+/// The controlling terminal, for drawing the TUI while stdout is captured
+fn open_tty() -> Result<File> {
+    let path = if cfg!(windows) { "CONOUT$" } else { "/dev/tty" };
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .context("--print needs a terminal to draw the TUI on")
+}
+
+/// Carry out what the TUI was left with, returning whether an item was accepted
+fn finish(outcome: Option<Outcome>, cache: &Cache) -> Result<bool> {
+    match outcome {
+        Some(Outcome::Open(selections)) => {
+            for selection in selections {
+                eprintln!("Opening: {}", selection.text);
+                open::that(&selection.text)?;
+                record_access(cache, &selection);
+            }
+        }
+        Some(Outcome::Print(selections)) => {
+            for selection in selections {
+                println!("{}", selection.text);
+                record_access(cache, &selection);
+            }
+        }
+        Some(Outcome::Run(commands)) => {
+            for command in commands {
+                eprintln!("Running: {}", command);
+                let status = shell_command(&command)
+                    .status()
+                    .with_context(|| format!("Failed to run {}", command))?;
+                if !status.success() {
+                    bail!("Command exited with {}: {}", status, command);
+                }
+            }
+        }
+        Some(Outcome::Copy(_)) | None => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+/// Record a repo access in the cache, for ranking
+fn record_access(cache: &Cache, selection: &Selection) {
+    if let Some(full_name) = &selection.full_name {
         let _ = cache.record_repo_access(full_name);
     }
+}
+
+/// OSC 52 escape sequence putting `text` on the system clipboard
+fn osc52(text: &str) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(text.len().div_ceil(3) * 4);
+    for chunk in text.as_bytes().chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    format!("\x1b]52;c;{}\x07", encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(id: i64, full_name: &str) -> Repo {
+        let (owner, name) = full_name.split_once('/').unwrap();
+        Repo::new(
+            id,
+            name.to_string(),
+            full_name.to_string(),
+            1,
+            owner.to_string(),
+            false,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_refresh_events_stream_into_matcher() {
+        let mut app = App::new(vec![repo(1, "org/a")], Vec::new());
+
+        app.on_refresh_event(RefreshEvent::Repos(vec![repo(1, "org/a"), repo(2, "org/b")]));
+        app.tick();
+        assert_eq!(app.total_repos(), 2);
+        assert_eq!(app.match_count(), 2);
+        assert_eq!(app.refresh_status(), Some(&RefreshStatus::Running { repos: 2 }));
+
+        app.on_refresh_event(RefreshEvent::Finished);
+        assert_eq!(app.refresh_status(), Some(&RefreshStatus::Finished { repos: 2 }));
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, crossterm::event::KeyModifiers::NONE)
+    }
 
-    Ok(())
+    fn type_pattern(app: &mut App, pattern: &str) {
+        for c in pattern.chars() {
+            app.handle_key_event(key(KeyCode::Char(c)));
+        }
+        app.tick();
+    }
+
+    fn names(app: &App) -> Vec<String> {
+        let mut names: Vec<String> = app.matches().iter().map(|item| item.full_name.clone()).collect();
+        names.sort();
+        names
+    }
+
+    fn with_mods(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_line_editing_keys() {
+        let mut app = App::new(vec![repo(1, "org/a")], Vec::new());
+        let ctrl = |c| with_mods(KeyCode::Char(c), KeyModifiers::CONTROL);
+        let alt = |c| with_mods(KeyCode::Char(c), KeyModifiers::ALT);
+        type_pattern(&mut app, "api gateway");
+
+        // Ctrl+a / Ctrl+e and inserting at the cursor
+        app.handle_key_event(ctrl('a'));
+        assert_eq!(app.input_cursor(), 0);
+        type_pattern(&mut app, "x ");
+        assert_eq!(app.input_pattern(), "x api gateway");
+        app.handle_key_event(ctrl('e'));
+        assert_eq!(app.input_cursor(), 13);
+
+        // Alt+b / Alt+f move by word, Delete removes under the cursor
+        app.handle_key_event(alt('b'));
+        assert_eq!(app.input_cursor(), 6);
+        app.handle_key_event(alt('b'));
+        app.handle_key_event(key(KeyCode::Delete));
+        assert_eq!(app.input_pattern(), "x pi gateway");
+        app.handle_key_event(alt('f'));
+        assert_eq!(app.input_cursor(), 4);
+
+        // Left / Right move within the input, Backspace deletes before the cursor
+        app.handle_key_event(key(KeyCode::Left));
+        app.handle_key_event(key(KeyCode::Backspace));
+        assert_eq!(app.input_pattern(), "x i gateway");
+        app.handle_key_event(key(KeyCode::Right));
+        assert_eq!(app.input_cursor(), 3);
+
+        // Ctrl+w deletes the word before the cursor, Ctrl+u everything before it
+        app.handle_key_event(ctrl('e'));
+        app.handle_key_event(ctrl('w'));
+        assert_eq!(app.input_pattern(), "x i ");
+        app.handle_key_event(key(KeyCode::Left));
+        app.handle_key_event(ctrl('u'));
+        assert_eq!((app.input_pattern(), app.input_cursor()), (" ", 0));
+
+        // Pastes go in at the cursor as a single line
+        app.on_paste("revsys/\ngg\n");
+        assert_eq!(app.input_pattern(), "revsys/ gg ");
+        assert_eq!(app.input_cursor(), 10);
+    }
+
+    #[test]
+    fn test_list_navigation_keys() {
+        let repos = (0..25).map(|i| repo(i, &format!("org/repo-{:02}", i))).collect();
+        let mut app = App::new(repos, Vec::new());
+        app.tick();
+        app.set_page_size(10);
+        let ctrl = |c| with_mods(KeyCode::Char(c), KeyModifiers::CONTROL);
+
+        app.handle_key_event(key(KeyCode::PageDown));
+        assert_eq!(app.selected_index(), 10);
+        app.handle_key_event(ctrl('n'));
+        assert_eq!(app.selected_index(), 11);
+        app.handle_key_event(ctrl('p'));
+        app.handle_key_event(key(KeyCode::PageUp));
+        assert_eq!(app.selected_index(), 0);
+        app.handle_key_event(key(KeyCode::End));
+        assert_eq!(app.selected_index(), 24);
+        app.handle_key_event(key(KeyCode::PageDown));
+        assert_eq!(app.selected_index(), 24);
+        app.handle_key_event(key(KeyCode::Home));
+        assert_eq!(app.selected_index(), 0);
+    }
+
+    #[test]
+    fn test_org_drill_down() {
+        let mut app = App::new(
+            vec![repo(1, "revsys/gg"), repo(2, "revsys/site"), repo(3, "other/revsys-fork")],
+            vec![Org::new(10, "revsys".to_string(), None, None)],
+        );
+        type_pattern(&mut app, "revsys is:org");
+        assert_eq!(names(&app), vec!["revsys/"]);
+
+        // Org views only apply to org items
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), crossterm::event::KeyModifiers::CONTROL);
+        assert_eq!(
+            app.handle_key_event(ctrl('g')),
+            Some(Outcome::Open(vec![Selection {
+                text: "https://github.com/orgs/revsys/teams".to_string(),
+                full_name: None,
+            }]))
+        );
+        assert_eq!(app.handle_key_event(ctrl('x')), None);
+
+        app.handle_key_event(key(KeyCode::Right));
+        app.tick();
+        assert_eq!(app.scope(), Some("revsys"));
+        assert_eq!(app.input_pattern(), "");
+        assert_eq!(names(&app), vec!["revsys/gg", "revsys/site"]);
+
+        type_pattern(&mut app, "site");
+        assert_eq!(names(&app), vec!["revsys/site"]);
+        assert_eq!(
+            app.handle_key_event(ctrl('x')),
+            Some(Outcome::Open(vec![Selection {
+                text: "https://github.com/revsys/site/actions".to_string(),
+                full_name: Some("revsys/site".to_string()),
+            }]))
+        );
+
+        // Backspace deletes the pattern first, then pops out of the org
+        for _ in 0..5 {
+            app.handle_key_event(key(KeyCode::Backspace));
+        }
+        app.tick();
+        assert_eq!(app.scope(), None);
+        assert_eq!(app.input_pattern(), "revsys is:org");
+
+        // Arrow keys drill down and pop back out too
+        app.handle_key_event(key(KeyCode::Right));
+        assert_eq!(app.scope(), Some("revsys"));
+        app.handle_key_event(key(KeyCode::Left));
+        assert_eq!(app.scope(), None);
+
+        // So does Tab, which only marks repos
+        app.tick();
+        app.handle_key_event(key(KeyCode::Tab));
+        assert_eq!(app.scope(), Some("revsys"));
+        assert_eq!(app.marked_count(), 0);
+    }
+
+    #[test]
+    fn test_configured_bindings() {
+        let config: Vec<crate::infrastructure::KeyBindingConfig> = serde_norway::from_str(
+            r#"
+- key: ctrl+k
+  copy: "{clone_url}"
+- key: alt+c
+  run: "code ~/src/{name}"
+- key: ctrl+b
+  open: "{url}/tree/{default_branch}"
+"#,
+        )
+        .unwrap();
+        let mut app = App::new(vec![repo(1, "revsys/it's")], Vec::new())
+            .with_keymap(KeyMap::new(&config).unwrap());
+        app.tick();
+
+        assert_eq!(
+            app.handle_key_event(with_mods(KeyCode::Char('k'), KeyModifiers::CONTROL)),
+            Some(Outcome::Copy(vec!["https://github.com/revsys/it's.git".to_string()]))
+        );
+        assert_eq!(
+            app.handle_key_event(with_mods(KeyCode::Char('c'), KeyModifiers::ALT)),
+            Some(Outcome::Run(vec![format!("code ~/src/{}", shell_quote("it's"))]))
+        );
+        assert_eq!(
+            app.handle_key_event(with_mods(KeyCode::Char('b'), KeyModifiers::CONTROL)),
+            Some(Outcome::Open(vec![Selection {
+                text: "https://github.com/revsys/it's/tree/HEAD".to_string(),
+                full_name: Some("revsys/it's".to_string()),
+            }]))
+        );
+        // Unbound keys with modifiers don't end up in the query
+        app.handle_key_event(with_mods(KeyCode::Char('z'), KeyModifiers::ALT));
+        assert_eq!(app.input_pattern(), "");
+    }
+
+    #[test]
+    fn test_accept_prints_with_print_template() {
+        let repos = vec![repo(1, "revsys/gg"), repo(2, "revsys/site")];
+        let mut app = App::new(repos.clone(), Vec::new());
+        app.set_query("gg".to_string());
+        app.finish_matching();
+        assert_eq!(app.match_count(), 1);
+        assert_eq!(
+            app.handle_key_event(key(KeyCode::Enter)),
+            Some(Outcome::Open(vec![Selection {
+                text: "https://github.com/revsys/gg".to_string(),
+                full_name: Some("revsys/gg".to_string()),
+            }]))
+        );
+
+        let mut app = App::new(repos.clone(), Vec::new()).with_print(Some("url".to_string()));
+        app.set_query("gg".to_string());
+        app.finish_matching();
+        assert_eq!(
+            app.accept(),
+            Some(Outcome::Print(vec![Selection {
+                text: "https://github.com/revsys/gg".to_string(),
+                full_name: Some("revsys/gg".to_string()),
+            }]))
+        );
+
+        let mut app = App::new(repos, Vec::new()).with_print(Some("~/src/{name}".to_string()));
+        type_pattern(&mut app, "site");
+        assert!(matches!(
+            app.handle_key_event(key(KeyCode::Enter)),
+            Some(Outcome::Print(selections)) if selections[0].text == "~/src/site"
+        ));
+    }
+
+    #[test]
+    fn test_marked_items() {
+        let mut app = App::new(
+            vec![repo(1, "revsys/api"), repo(2, "revsys/web"), repo(3, "revsys/docs")],
+            vec![Org::new(10, "revsys".to_string(), None, None)],
+        )
+        .with_print(Some("name".to_string()));
+        type_pattern(&mut app, "-is:org");
+        let selected = app.selected_item().unwrap().full_name.clone();
+
+        // Tab marks and moves down, Shift+Tab moves up and pressing either again unmarks
+        let back_tab = with_mods(KeyCode::BackTab, KeyModifiers::SHIFT);
+        app.handle_key_event(key(KeyCode::Tab));
+        app.handle_key_event(back_tab);
+        assert_eq!(app.marked_count(), 2);
+        app.handle_key_event(back_tab);
+        assert_eq!(app.marked_count(), 1);
+        assert!(!app.is_marked(app.matches()[0]));
+        app.handle_key_event(key(KeyCode::Tab));
+        assert_eq!(app.marked_count(), 2);
+        assert!(app.is_marked(app.matches()[0]));
+
+        // Marks survive a new query, and views and Enter act on all of them
+        type_pattern(&mut app, " docs");
+        let Some(Outcome::Open(selections)) =
+            app.handle_key_event(with_mods(KeyCode::Char('x'), KeyModifiers::CONTROL))
+        else {
+            panic!("expected the actions pages to open");
+        };
+        assert_eq!(selections.len(), 2);
+        assert_eq!(selections[1].text, format!("https://github.com/{}/actions", selected));
+        let Some(Outcome::Print(selections)) = app.handle_key_event(key(KeyCode::Enter)) else {
+            panic!("expected the marked items to print");
+        };
+        assert_eq!(selections.len(), 2);
+        assert!(!selections.iter().any(|selection| selection.text == "docs"));
+    }
+
+    #[test]
+    fn test_action_menu() {
+        let mut app = App::new(vec![repo(1, "revsys/gg"), repo(2, "revsys/site")], Vec::new());
+        type_pattern(&mut app, "revsys/gg");
+
+        // → at the end of the query pops up the menu, which takes the keys until it closes
+        app.handle_key_event(key(KeyCode::Right));
+        assert_eq!(app.menu().map(ActionMenu::title), Some("revsys/gg"));
+        app.handle_key_event(key(KeyCode::Esc));
+        assert!(app.menu().is_none());
+        assert!(!app.should_exit());
+
+        app.handle_key_event(with_mods(KeyCode::Char('o'), KeyModifiers::CONTROL));
+        type_pattern(&mut app, "releases");
+        assert_eq!(app.input_pattern(), "revsys/gg");
+        assert_eq!(
+            app.handle_key_event(key(KeyCode::Enter)),
+            Some(Outcome::Open(vec![Selection {
+                text: "https://github.com/revsys/gg/releases".to_string(),
+                full_name: Some("revsys/gg".to_string()),
+            }]))
+        );
+        assert!(app.menu().is_none());
+    }
+
+    #[test]
+    fn test_osc52() {
+        assert_eq!(osc52("revsys/gg"), "\x1b]52;c;cmV2c3lzL2dn\x07");
+        assert_eq!(osc52("ab"), "\x1b]52;c;YWI=\x07");
+    }
 }
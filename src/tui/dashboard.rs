@@ -0,0 +1,243 @@
+use super::ui;
+use crate::application::{dashboard_repos, spawn_dashboard_poll, DashboardEvent};
+use crate::infrastructure::{Cache, WorkflowRun};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+use std::io::{self, IsTerminal};
+use std::time::Duration;
+
+/// What the dashboard knows about a repo's latest run
+#[derive(Debug)]
+pub enum RowState {
+    /// Waiting for the first poll
+    Loading,
+    /// The repo has never run a workflow
+    NoRuns,
+    Run(Box<WorkflowRun>),
+    Failed(String),
+}
+
+/// One repo on the dashboard
+#[derive(Debug)]
+pub struct DashboardRow {
+    pub full_name: String,
+    pub state: RowState,
+}
+
+/// Actions dashboard state
+pub struct Dashboard {
+    rows: Vec<DashboardRow>,
+    selected_index: usize,
+    should_exit: bool,
+    last_poll: Option<DateTime<Utc>>,
+}
+
+impl Dashboard {
+    /// Create a dashboard for these repos, in display order
+    pub fn new(repos: Vec<String>) -> Self {
+        Self {
+            rows: repos
+                .into_iter()
+                .map(|full_name| DashboardRow {
+                    full_name,
+                    state: RowState::Loading,
+                })
+                .collect(),
+            selected_index: 0,
+            should_exit: false,
+            last_poll: None,
+        }
+    }
+
+    /// Rows in display order
+    pub fn rows(&self) -> &[DashboardRow] {
+        &self.rows
+    }
+
+    /// Get the selected index
+    pub fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    /// When every repo was last polled
+    pub fn last_poll(&self) -> Option<DateTime<Utc>> {
+        self.last_poll
+    }
+
+    /// Check if the dashboard should exit
+    pub fn should_exit(&self) -> bool {
+        self.should_exit
+    }
+
+    /// Apply an update from the poller
+    pub fn on_event(&mut self, event: DashboardEvent) {
+        let (full_name, state) = match event {
+            DashboardEvent::Run { full_name, run } => {
+                (full_name, run.map_or(RowState::NoRuns, RowState::Run))
+            }
+            DashboardEvent::Failed { full_name, message } => (full_name, RowState::Failed(message)),
+            DashboardEvent::Polled => {
+                self.last_poll = Some(Utc::now());
+                return;
+            }
+        };
+
+        if let Some(row) = self.rows.iter_mut().find(|row| row.full_name == full_name) {
+            row.state = state;
+        }
+    }
+
+    /// Handle a key event, returning the repo and URL to open on Enter
+    pub fn handle_key_event(&mut self, key: KeyEvent) -> Option<(String, String)> {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if !self.rows.is_empty() {
+                    self.selected_index = (self.selected_index + 1).min(self.rows.len() - 1);
+                }
+                None
+            }
+            KeyCode::Enter => {
+                let row = self.rows.get(self.selected_index)?;
+                // Fall back to the Actions page until a run is known
+                let url = match &row.state {
+                    RowState::Run(run) => run.html_url.clone(),
+                    _ => format!("https://github.com/{}/actions", row.full_name),
+                };
+                Some((row.full_name.clone(), url))
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.should_exit = true;
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Run the Actions dashboard for the `count` most frecent repos, polling every `interval`
+pub fn run_dashboard(cache: Cache, token: String, count: usize, interval: Duration) -> Result<()> {
+    if !io::stdout().is_terminal() {
+        anyhow::bail!(
+            "TUI requires a terminal (TTY). Please run this command in an interactive terminal."
+        );
+    }
+
+    let repos: Vec<String> = dashboard_repos(cache.load_repos()?, count)
+        .into_iter()
+        .map(|repo| repo.full_name)
+        .collect();
+    if repos.is_empty() {
+        anyhow::bail!("No repos in the cache yet, run `gg data refresh` first");
+    }
+
+    let events = spawn_dashboard_poll(token, repos.clone(), interval);
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let mut dashboard = Dashboard::new(repos);
+    let result = loop {
+        while let Ok(event) = events.try_recv() {
+            dashboard.on_event(event);
+        }
+
+        terminal.draw(|f| ui::render_dashboard(f, &dashboard))?;
+
+        if dashboard.should_exit() {
+            break None;
+        }
+
+        match event::poll(Duration::from_millis(100)) {
+            Ok(true) => {
+                if let Event::Key(key) = event::read()? {
+                    if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                        break None;
+                    }
+                    if let Some(selected) = dashboard.handle_key_event(key) {
+                        break Some(selected);
+                    }
+                }
+            }
+            Ok(false) => {}
+            Err(e) => {
+                let _ = disable_raw_mode();
+                let _ = execute!(terminal.backend_mut(), LeaveAlternateScreen);
+                return Err(e.into());
+            }
+        }
+    };
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+
+    if let Some((full_name, url)) = result {
+        eprintln!("Opening: {}", url);
+        open::that(&url)?;
+        let _ = cache.record_repo_access(&full_name);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(id: u64) -> Box<WorkflowRun> {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "name": "CI", "status": "completed", "conclusion": "success",
+            "head_branch": "main", "head_sha": "abc123", "event": "push",
+            "html_url": format!("https://github.com/org/b/actions/runs/{}", id),
+            "created_at": "2026-01-01T10:00:00Z", "updated_at": "2026-01-01T10:03:00Z"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_events_update_rows_and_enter_opens_run() {
+        let mut dashboard = Dashboard::new(vec!["org/a".to_string(), "org/b".to_string()]);
+
+        dashboard.on_event(DashboardEvent::Run {
+            full_name: "org/b".to_string(),
+            run: Some(run(7)),
+        });
+        dashboard.on_event(DashboardEvent::Run {
+            full_name: "org/a".to_string(),
+            run: None,
+        });
+        dashboard.on_event(DashboardEvent::Polled);
+
+        assert!(matches!(dashboard.rows()[0].state, RowState::NoRuns));
+        assert!(matches!(dashboard.rows()[1].state, RowState::Run(_)));
+        assert!(dashboard.last_poll().is_some());
+
+        let enter = KeyEvent::from(KeyCode::Enter);
+        assert_eq!(
+            dashboard.handle_key_event(enter),
+            Some(("org/a".to_string(), "https://github.com/org/a/actions".to_string()))
+        );
+
+        dashboard.handle_key_event(KeyEvent::from(KeyCode::Down));
+        assert_eq!(
+            dashboard.handle_key_event(enter),
+            Some((
+                "org/b".to_string(),
+                "https://github.com/org/b/actions/runs/7".to_string()
+            ))
+        );
+    }
+}
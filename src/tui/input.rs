@@ -0,0 +1,131 @@
+/// Single line text input with a cursor and readline-style editing
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineEditor {
+    text: String,
+    /// Byte offset of the cursor, always on a char boundary
+    cursor: usize,
+}
+
+impl LineEditor {
+    /// Current text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Number of characters before the cursor
+    pub fn cursor(&self) -> usize {
+        self.text[..self.cursor].chars().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.cursor == self.text.len()
+    }
+
+    /// Replace the text, leaving the cursor at the end
+    pub fn set(&mut self, text: String) {
+        self.cursor = text.len();
+        self.text = text;
+    }
+
+    /// Take the text, leaving the editor empty
+    pub fn take(&mut self) -> String {
+        self.cursor = 0;
+        std::mem::take(&mut self.text)
+    }
+
+    pub fn insert(&mut self, c: char) {
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    /// Insert pasted text, flattening line breaks and tabs to spaces
+    pub fn insert_str(&mut self, text: &str) {
+        let flat: String = text
+            .trim_end_matches(['\r', '\n'])
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+        self.text.insert_str(self.cursor, &flat);
+        self.cursor += flat.len();
+    }
+
+    /// Delete the character before the cursor
+    pub fn backspace(&mut self) {
+        if let Some(c) = self.text[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+            self.text.remove(self.cursor);
+        }
+    }
+
+    /// Delete the character under the cursor
+    pub fn delete(&mut self) {
+        if self.cursor < self.text.len() {
+            self.text.remove(self.cursor);
+        }
+    }
+
+    pub fn left(&mut self) {
+        if let Some(c) = self.text[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+        }
+    }
+
+    pub fn right(&mut self) {
+        if let Some(c) = self.text[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
+        }
+    }
+
+    pub fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.cursor = self.text.len();
+    }
+
+    /// Move to the start of the current or previous word (Alt+B)
+    pub fn word_left(&mut self) {
+        self.cursor = self.boundary_before(|c| !c.is_alphanumeric());
+    }
+
+    /// Move past the end of the current or next word (Alt+F)
+    pub fn word_right(&mut self) {
+        let rest = &self.text[self.cursor..];
+        let word_start = rest
+            .find(char::is_alphanumeric)
+            .unwrap_or(rest.len());
+        let word_len = rest[word_start..]
+            .find(|c: char| !c.is_alphanumeric())
+            .unwrap_or(rest.len() - word_start);
+        self.cursor += word_start + word_len;
+    }
+
+    /// Delete back to the previous whitespace (Ctrl+W)
+    pub fn delete_word(&mut self) {
+        let start = self.boundary_before(char::is_whitespace);
+        self.text.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    /// Delete everything before the cursor (Ctrl+U)
+    pub fn delete_to_start(&mut self) {
+        self.text.replace_range(..self.cursor, "");
+        self.cursor = 0;
+    }
+
+    /// Start of the word before the cursor, skipping separators first
+    fn boundary_before(&self, is_separator: impl Fn(char) -> bool) -> usize {
+        let before = &self.text[..self.cursor];
+        let word_end = before
+            .trim_end_matches(|c: char| is_separator(c))
+            .len();
+        before[..word_end]
+            .rfind(|c: char| is_separator(c))
+            .map_or(0, |i| i + before[i..].chars().next().map_or(0, char::len_utf8))
+    }
+}
@@ -0,0 +1,454 @@
+use super::matcher::RepoItem;
+use crate::infrastructure::{BindingAction, BindingTarget, KeyBindingConfig};
+use anyhow::{bail, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fmt;
+
+/// Built-in editing, navigation and UI commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    First,
+    Last,
+    /// Cursor left, or back out of an org on an empty query
+    Left,
+    /// Cursor right, or at the end of the query drill down into the selected org
+    /// or pop up the action menu for the selected repo
+    Right,
+    LineStart,
+    LineEnd,
+    WordLeft,
+    WordRight,
+    /// Delete before the cursor, or back out of an org on an empty query
+    Backspace,
+    Delete,
+    DeleteWord,
+    DeleteToStart,
+    /// Open the marked or selected items, or print them with `--print`
+    Accept,
+    /// Mark or unmark the selected item, then move down
+    MarkDown,
+    /// Mark or unmark the selected item, then move up
+    MarkUp,
+    /// Pop up the menu of views and actions for the selected item
+    ActionMenu,
+    ToggleHelp,
+    TogglePreview,
+    /// Close the help popup, or quit
+    Escape,
+    Quit,
+}
+
+/// What a bound key does
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyAction {
+    Command(Command),
+    /// Open the expanded URL template in the browser
+    Open(String),
+    /// Copy the expanded template to the clipboard
+    Copy(String),
+    /// Leave the TUI and run the expanded shell command
+    Run(String),
+}
+
+impl From<BindingAction> for KeyAction {
+    fn from(action: BindingAction) -> Self {
+        match action {
+            BindingAction::Open(template) => Self::Open(template),
+            BindingAction::Copy(template) => Self::Copy(template),
+            BindingAction::Run(template) => Self::Run(template),
+        }
+    }
+}
+
+/// A key with its modifiers, e.g. `Ctrl+k`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // Shift+Tab arrives as BackTab, with or without Shift
+        let code = match code {
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            code => code,
+        };
+        // Shift is already part of the character, and terminals disagree on reporting it
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers.difference(KeyModifiers::SHIFT),
+            _ => modifiers,
+        };
+        // Ctrl and Alt combos arrive lowercase
+        let code = match code {
+            KeyCode::Char(c) if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                KeyCode::Char(c.to_ascii_lowercase())
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    fn key(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> Self {
+        Self::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    fn alt(c: char) -> Self {
+        Self::new(KeyCode::Char(c), KeyModifiers::ALT)
+    }
+
+    /// Parse a chord like `ctrl+k`, `alt+shift+x`, `pagedown` or `f5`
+    pub fn parse(value: &str) -> Result<Self> {
+        let (modifier_names, key) = if let Some(rest) = value.strip_suffix("++") {
+            // The key itself is `+`, as in `ctrl++`
+            (rest, "+")
+        } else {
+            match value.rsplit_once('+') {
+                Some((_, "")) if value != "+" => bail!("Missing key in `{}`", value),
+                Some((rest, key)) if !key.is_empty() => (rest, key),
+                _ => ("", value),
+            }
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_names.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match name.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => bail!("Unknown modifier `{}` in key `{}`", name, value),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "tab" => KeyCode::Tab,
+                "esc" | "escape" => KeyCode::Esc,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => bail!("Unknown key `{}` in `{}`", key, value),
+                },
+            },
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl From<&KeyEvent> for KeyChord {
+    fn from(event: &KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Up => f.write_str("↑"),
+            KeyCode::Down => f.write_str("↓"),
+            KeyCode::Left => f.write_str("←"),
+            KeyCode::Right => f.write_str("→"),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            KeyCode::Backspace => f.write_str("Bksp"),
+            KeyCode::Delete => f.write_str("Del"),
+            KeyCode::BackTab => f.write_str("Shift+Tab"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            code => write!(f, "{}", code),
+        }
+    }
+}
+
+/// A key bound to an action
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub key: KeyChord,
+    /// Items the binding applies to, commands use `Any`
+    pub target: BindingTarget,
+    /// Shown in the help popup
+    pub description: String,
+    pub action: KeyAction,
+}
+
+impl Binding {
+    fn command(key: KeyChord, command: Command, description: &str) -> Self {
+        Self {
+            key,
+            target: BindingTarget::Any,
+            description: description.to_string(),
+            action: KeyAction::Command(command),
+        }
+    }
+
+    fn open(key: KeyChord, target: BindingTarget, template: &str, description: &str) -> Self {
+        Self {
+            key,
+            target,
+            description: description.to_string(),
+            action: KeyAction::Open(template.to_string()),
+        }
+    }
+
+    /// Whether the binding applies with the selected item, `is_org` is None without one
+    fn applies_to(&self, is_org: Option<bool>) -> bool {
+        applies_to(self.target, is_org)
+    }
+}
+
+/// Whether an action for `target` applies to an item, `is_org` is None without one
+pub fn applies_to(target: BindingTarget, is_org: Option<bool>) -> bool {
+    match target {
+        BindingTarget::Any => true,
+        BindingTarget::Repo => is_org == Some(false),
+        BindingTarget::Org => is_org == Some(true),
+    }
+}
+
+/// Registry of the TUI's key bindings, the built-in ones plus any from the settings file
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        use BindingTarget::{Org, Repo};
+        use Command::*;
+
+        let bindings = vec![
+            Binding::command(KeyChord::key(KeyCode::Enter), Accept, "Open in browser (or print with --print)"),
+            Binding::command(KeyChord::key(KeyCode::Up), Up, "Move up"),
+            Binding::command(KeyChord::ctrl('p'), Up, "Move up"),
+            Binding::command(KeyChord::key(KeyCode::Down), Down, "Move down"),
+            Binding::command(KeyChord::ctrl('n'), Down, "Move down"),
+            Binding::command(KeyChord::key(KeyCode::PageUp), PageUp, "Page up"),
+            Binding::command(KeyChord::key(KeyCode::PageDown), PageDown, "Page down"),
+            Binding::command(KeyChord::key(KeyCode::Home), First, "First match"),
+            Binding::command(KeyChord::key(KeyCode::End), Last, "Last match"),
+            Binding::command(KeyChord::key(KeyCode::Tab), MarkDown, "Mark/unmark and move down, drill down on an org"),
+            Binding::command(KeyChord::key(KeyCode::BackTab), MarkUp, "Mark/unmark and move up"),
+            Binding::command(KeyChord::key(KeyCode::Right), Right, "Cursor right, drill down or menu at the end"),
+            Binding::command(KeyChord::ctrl('o'), ActionMenu, "Menu of views and actions"),
+            Binding::command(KeyChord::key(KeyCode::Left), Left, "Cursor left, back out of the org"),
+            Binding::command(KeyChord::key(KeyCode::Backspace), Backspace, "Delete back, back out of the org"),
+            Binding::command(KeyChord::key(KeyCode::Delete), Delete, "Delete under the cursor"),
+            Binding::command(KeyChord::ctrl('a'), LineStart, "Start of the query"),
+            Binding::command(KeyChord::ctrl('e'), LineEnd, "End of the query"),
+            Binding::command(KeyChord::alt('b'), WordLeft, "Word left"),
+            Binding::command(KeyChord::alt('f'), WordRight, "Word right"),
+            Binding::command(KeyChord::ctrl('w'), DeleteWord, "Delete the previous word"),
+            Binding::command(KeyChord::ctrl('u'), DeleteToStart, "Delete to the start"),
+            Binding::command(KeyChord::ctrl('t'), TogglePreview, "Toggle the preview pane"),
+            Binding::command(KeyChord::key(KeyCode::Char('?')), ToggleHelp, "Show/hide this help"),
+            Binding::command(KeyChord::key(KeyCode::Esc), Escape, "Close help or exit"),
+            Binding::command(KeyChord::ctrl('c'), Quit, "Exit"),
+            Binding::command(KeyChord::ctrl('d'), Quit, "Exit"),
+            Binding::open(KeyChord::ctrl('b'), Repo, "{url}/issues", "Issues"),
+            Binding::open(KeyChord::ctrl('m'), Repo, "{url}/milestones", "Milestones"),
+            Binding::open(KeyChord::ctrl('r'), Repo, "{url}/pulls", "Pull Requests"),
+            Binding::open(KeyChord::ctrl('x'), Repo, "{url}/actions", "Actions"),
+            Binding::open(KeyChord::ctrl('l'), Org, "https://github.com/orgs/{owner}/people", "People"),
+            Binding::open(KeyChord::ctrl('g'), Org, "https://github.com/orgs/{owner}/teams", "Teams"),
+            Binding::open(KeyChord::ctrl('r'), Org, "https://github.com/orgs/{owner}/projects", "Projects"),
+            Binding::open(
+                KeyChord::ctrl('s'),
+                Org,
+                "https://github.com/organizations/{owner}/settings/profile",
+                "Settings",
+            ),
+        ];
+
+        Self { bindings }
+    }
+}
+
+impl KeyMap {
+    /// The built-in bindings with `custom` ones replacing those on the same key
+    pub fn new(custom: &[KeyBindingConfig]) -> Result<Self> {
+        let mut keymap = Self::default();
+        for config in custom {
+            let key = KeyChord::parse(&config.key)
+                .with_context(|| format!("Invalid key binding `{}`", config.key))?;
+            let action = KeyAction::from(config.action.clone());
+            let description = config.description.clone().unwrap_or_else(|| match &action {
+                KeyAction::Open(template) => format!("Open {}", template),
+                KeyAction::Copy(template) => format!("Copy {}", template),
+                KeyAction::Run(template) => format!("Run {}", template),
+                KeyAction::Command(_) => unreachable!("commands can't be configured"),
+            });
+
+            // Drop built-ins the new binding would shadow for any item
+            keymap.bindings.retain(|binding| {
+                binding.key != key
+                    || !(binding.target == config.on
+                        || binding.target == BindingTarget::Any
+                        || config.on == BindingTarget::Any)
+            });
+            keymap.bindings.push(Binding {
+                key,
+                target: config.on,
+                description,
+                action,
+            });
+        }
+        Ok(keymap)
+    }
+
+    /// Action bound to `key` for the selected item, `is_org` is None without one
+    pub fn lookup(&self, key: &KeyEvent, is_org: Option<bool>) -> Option<&Binding> {
+        let chord = KeyChord::from(key);
+        self.bindings
+            .iter()
+            .find(|binding| binding.key == chord && binding.applies_to(is_org))
+    }
+
+    /// Every binding, in help popup order
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+}
+
+/// Fill in `{url}`, `{full_name}`, `{owner}`, `{name}`, `{default_branch}`, `{clone_url}`
+/// and `{ssh_url}` for `item`, passing each value through `quote`
+pub fn expand_template(template: &str, item: &RepoItem, quote: impl Fn(&str) -> String) -> String {
+    // Org items are `org/`, which shouldn't leave `//` in URLs
+    let full_name = item.full_name.trim_end_matches('/');
+    let repo = &item.repo;
+    template
+        .replace("{url}", &quote(item.url.trim_end_matches('/')))
+        .replace("{full_name}", &quote(full_name))
+        .replace("{owner}", &quote(&repo.owner_login))
+        .replace("{name}", &quote(&repo.name))
+        .replace(
+            "{default_branch}",
+            &quote(repo.default_branch.as_deref().unwrap_or("HEAD")),
+        )
+        .replace("{clone_url}", &quote(&format!("https://github.com/{}.git", full_name)))
+        .replace("{ssh_url}", &quote(&format!("git@github.com:{}.git", full_name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Repo;
+
+    fn item(full_name: &str) -> RepoItem {
+        let (owner, name) = full_name.split_once('/').unwrap();
+        let mut repo = Repo::new(
+            1,
+            name.to_string(),
+            full_name.to_string(),
+            1,
+            owner.to_string(),
+            false,
+            None,
+            None,
+            None,
+        );
+        repo.default_branch = (!name.is_empty()).then(|| "develop".to_string());
+        RepoItem::new(repo)
+    }
+
+    #[test]
+    fn test_parse_key_chord() {
+        assert_eq!(KeyChord::parse("ctrl+K").unwrap(), KeyChord::ctrl('k'));
+        assert_eq!(KeyChord::parse("Alt+b").unwrap(), KeyChord::alt('b'));
+        assert_eq!(KeyChord::parse("pagedown").unwrap(), KeyChord::key(KeyCode::PageDown));
+        assert_eq!(KeyChord::parse("F5").unwrap().to_string(), "F5");
+        assert_eq!(KeyChord::parse("ctrl++").unwrap().to_string(), "Ctrl++");
+        assert!(KeyChord::parse("hyper+k").is_err());
+        assert!(KeyChord::parse("f13").is_err());
+
+        // Shift is folded into the character, however the terminal reports it
+        let event = KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT);
+        assert_eq!(KeyChord::from(&event), KeyChord::key(KeyCode::Char('?')));
+        let event = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+        assert_eq!(KeyChord::from(&event), KeyChord::parse("shift+tab").unwrap());
+        assert_eq!(KeyChord::from(&event).to_string(), "Shift+Tab");
+    }
+
+    #[test]
+    fn test_custom_bindings_replace_built_ins() {
+        let config: Vec<KeyBindingConfig> = serde_norway::from_str(
+            r#"
+- key: ctrl+b
+  open: "{url}/security"
+- key: ctrl+k
+  on: any
+  copy: "{full_name}"
+"#,
+        )
+        .unwrap();
+        let keymap = KeyMap::new(&config).unwrap();
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+
+        assert_eq!(
+            keymap.lookup(&ctrl('b'), Some(false)).map(|b| &b.action),
+            Some(&KeyAction::Open("{url}/security".to_string()))
+        );
+        assert_eq!(
+            keymap.lookup(&ctrl('k'), Some(true)).map(|b| &b.action),
+            Some(&KeyAction::Copy("{full_name}".to_string()))
+        );
+        // Repo and org views only apply with a matching item selected
+        assert_eq!(
+            keymap.lookup(&ctrl('r'), Some(true)).map(|b| &b.action),
+            Some(&KeyAction::Open("https://github.com/orgs/{owner}/projects".to_string()))
+        );
+        assert_eq!(keymap.lookup(&ctrl('r'), None).map(|b| &b.action), None);
+        assert_eq!(keymap.bindings().iter().filter(|b| b.key == KeyChord::ctrl('b')).count(), 1);
+
+        let bad: Vec<KeyBindingConfig> = serde_norway::from_str("- key: ctrl+\n  run: ls").unwrap();
+        assert!(KeyMap::new(&bad).is_err());
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let repo = item("revsys/gg");
+        assert_eq!(
+            expand_template("{url}/tree/{default_branch}", &repo, str::to_string),
+            "https://github.com/revsys/gg/tree/develop"
+        );
+        assert_eq!(
+            expand_template("git clone {ssh_url} {name}", &repo, |v| format!("'{}'", v)),
+            "git clone 'git@github.com:revsys/gg.git' 'gg'"
+        );
+        assert_eq!(
+            expand_template("{url}/people {full_name}", &item("revsys/"), str::to_string),
+            "https://github.com/revsys/people revsys"
+        );
+    }
+}
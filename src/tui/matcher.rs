@@ -1,8 +1,10 @@
-use crate::domain::{Org, Repo};
+use crate::domain::{Org, Repo, RepoQuery};
 use nucleo::{
     pattern::{CaseMatching, Normalization},
-    Config, Utf32String,
+    Config, Matcher, Utf32String,
 };
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 /// A repo item that can be fuzzy matched
@@ -25,16 +27,28 @@ impl RepoItem {
             url,
         }
     }
+
+    /// Whether this is an `org/` pseudo-item rather than a repo
+    pub fn is_org(&self) -> bool {
+        self.repo.name.is_empty()
+    }
 }
 
 /// Fuzzy matcher for repositories using nucleo
 pub struct RepoMatcher {
-    /// Nucleo matcher worker (runs matching in background)
-    nucleo: nucleo::Nucleo<String>,
-    /// Current pattern string
-    pattern: String,
-    /// All repo items for lookup by index
+    /// Nucleo matcher worker (runs matching in background), holding indices into `items`
+    /// of the items that pass the query's qualifiers
+    nucleo: nucleo::Nucleo<u32>,
+    /// Current query, its fuzzy text is nucleo's pattern
+    query: RepoQuery,
+    /// All repo items, indexed by the value injected into nucleo
     items: Vec<RepoItem>,
+    /// Names already injected, so refreshed data is not added twice
+    known: HashSet<String>,
+    /// Scores matched items against the pattern, since nucleo's snapshot only orders them
+    scorer: RefCell<Matcher>,
+    /// Indices into `items` of the matches, best first, re-ranked when a tick changes them
+    ranked: Vec<u32>,
 }
 
 impl RepoMatcher {
@@ -44,102 +58,172 @@ impl RepoMatcher {
 
         // Create the nucleo matcher
         let nucleo = nucleo::Nucleo::new(
-            config,
+            config.clone(),
             Arc::new(|| {}), // No notification needed
             None,            // Use default number of threads
             1,               // Number of columns for display (must be at least 1)
         );
 
-        // Collect all full_names for injection
-        let mut all_names: Vec<String> = Vec::new();
-        for repo in &repos {
-            all_names.push(repo.full_name.clone());
-        }
-        for org in &orgs {
-            all_names.push(format!("{}/", org.login));
-        }
+        let mut matcher = Self {
+            nucleo,
+            query: RepoQuery::default(),
+            items: Vec::new(),
+            known: HashSet::new(),
+            scorer: RefCell::new(Matcher::new(config)),
+            ranked: Vec::new(),
+        };
+        matcher.push_repos(repos);
+        matcher.push_orgs(orgs);
+        matcher
+    }
 
-        // Inject items
-        let injector = nucleo.injector();
-        for name in all_names {
-            injector.push(name, |data, columns| {
-                // Fill the first column with the data for matching
-                columns[0] = Utf32String::from(data.as_str());
-            });
-        }
+    /// Add repos to the matcher, skipping any that are already present
+    /// Returns the number of new items
+    pub fn push_repos(&mut self, repos: Vec<Repo>) -> usize {
+        repos
+            .into_iter()
+            .map(|repo| self.push_item(RepoItem::new(repo)))
+            .filter(|added| *added)
+            .count()
+    }
 
-        // Create items map for lookup
-        let mut items = Vec::new();
-        for repo in repos {
-            items.push(RepoItem::new(repo));
-        }
-        for org in orgs {
-            // Create a pseudo-repo item for the org
-            let pseudo_repo = Repo {
-                id: org.id,
-                name: String::new(),
-                full_name: format!("{}/", org.login),
-                owner_id: org.id,
-                owner_login: org.login.clone(),
-                private: false,
-                description: None,
-                language: None,
-                default_branch: None,
-                last_accessed_at: org.last_accessed_at,
-                access_count: org.access_count,
-            };
-            items.push(RepoItem::new(pseudo_repo));
+    /// Add orgs as `org/` pseudo-items, skipping any that are already present
+    /// Returns the number of new items
+    pub fn push_orgs(&mut self, orgs: Vec<Org>) -> usize {
+        orgs.into_iter()
+            .map(|org| {
+                // Create a pseudo-repo item for the org
+                let pseudo_repo = Repo {
+                    id: org.id,
+                    name: String::new(),
+                    full_name: format!("{}/", org.login),
+                    owner_id: org.id,
+                    owner_login: org.login.clone(),
+                    private: false,
+                    description: None,
+                    language: None,
+                    default_branch: None,
+                    fork: false,
+                    last_accessed_at: org.last_accessed_at,
+                    access_count: org.access_count,
+                };
+                self.push_item(RepoItem::new(pseudo_repo))
+            })
+            .filter(|added| *added)
+            .count()
+    }
+
+    /// Add a single item, returning false if it was already known
+    fn push_item(&mut self, item: RepoItem) -> bool {
+        if !self.known.insert(item.full_name.clone()) {
+            return false;
         }
 
-        Self {
-            nucleo,
-            pattern: String::new(),
-            items,
+        self.items.push(item);
+        self.inject(self.items.len() - 1);
+        true
+    }
+
+    /// Inject the item at `index` into nucleo if it passes the query's qualifiers
+    fn inject(&self, index: usize) {
+        let item = &self.items[index];
+        if !self.query.matches(&item.repo, item.is_org()) {
+            return;
         }
+
+        self.nucleo.injector().push(index as u32, |_, columns| {
+            // Fill the first column with the data for matching
+            columns[0] = Utf32String::from(item.full_name.as_str());
+        });
     }
 
-    /// Update the search pattern
+    /// Update the search pattern, which may contain qualifiers like `org:revsys`
     pub fn update_pattern(&mut self, pattern: String) {
-        self.pattern = pattern.clone();
+        let query = RepoQuery::parse(&pattern);
+
+        // Qualifiers filter items before matching, so changing them means reinjecting
+        let filters_changed = !query.same_filters(&self.query);
+        // Nucleo only narrows the previous matches when told the old pattern is a prefix
+        let append = !filters_changed && query.text.starts_with(&self.query.text);
+        self.query = query;
+
+        if filters_changed {
+            self.nucleo.restart(true);
+            for index in 0..self.items.len() {
+                self.inject(index);
+            }
+        }
         self.nucleo.pattern.reparse(
             0,                              // column index
-            &pattern,
+            &self.query.text,
             CaseMatching::Ignore,
             Normalization::Smart,
-            true, // append
+            append,
         );
     }
 
     /// Tick the matcher (process pending pattern changes)
     pub fn tick(&mut self) {
-        self.nucleo.tick(100); // 100ms timeout
+        // 100ms timeout
+        if self.nucleo.tick(100).changed {
+            self.rank();
+        }
+    }
+
+    /// Tick until matching has caught up with the current pattern
+    pub fn finish(&mut self) {
+        let mut changed = false;
+        loop {
+            let status = self.nucleo.tick(100);
+            changed |= status.changed;
+            if !status.running {
+                break;
+            }
+        }
+        if changed {
+            self.rank();
+        }
     }
 
     /// Get the current matches as a sorted vector
     pub fn matches_sorted(&self) -> Vec<&RepoItem> {
+        self.ranked.iter().map(|&index| &self.items[index as usize]).collect()
+    }
+
+    /// Score and sort nucleo's matches, after a tick changed them
+    fn rank(&mut self) {
         let snapshot = self.nucleo.snapshot();
         let matched_count = snapshot.matched_item_count();
+        let pattern = snapshot.pattern().column_pattern(0);
+        let mut scorer = self.scorer.borrow_mut();
 
-        let mut matches: Vec<_> = snapshot
+        let mut matches: Vec<(u32, f64)> = snapshot
             .matched_items(0..matched_count)
-            .filter_map(|item| {
-                // Find the corresponding RepoItem by matching the full_name
-                self.items.iter().find(|ri| &ri.full_name == item.data).map(|ri| {
-                    // For now, use a default fuzzy score since Item doesn't have a score field
-                    // In a more sophisticated implementation, we could use matcher_columns
-                    (ri, 100.0_f64)
-                })
+            .map(|item| {
+                let fuzzy_score = pattern
+                    .score(item.matcher_columns[0].slice(..), &mut scorer)
+                    .unwrap_or(0);
+                let repo_item = &self.items[*item.data as usize];
+                (*item.data, self.combined_score(repo_item, fuzzy_score as f64))
             })
             .collect();
 
         // Sort by combined score (fuzzy match score + usage score)
-        matches.sort_by(|a, b| {
-            let score_a = self.combined_score(a.0, a.1);
-            let score_b = self.combined_score(b.0, b.1);
-            score_b.partial_cmp(&score_a).unwrap_or(std::cmp::Ordering::Equal)
-        });
+        matches.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        self.ranked = matches.into_iter().map(|(index, _)| index).collect();
+    }
 
-        matches.into_iter().map(|(item, _)| item).collect()
+    /// Positions of the characters in `item.full_name` matched by the current pattern, ascending
+    pub fn match_indices(&self, item: &RepoItem) -> Vec<u32> {
+        let pattern = self.nucleo.snapshot().pattern().column_pattern(0);
+        let haystack = Utf32String::from(item.full_name.as_str());
+        let mut indices = Vec::new();
+        pattern.indices(haystack.slice(..), &mut self.scorer.borrow_mut(), &mut indices);
+        // Each pattern atom appends its own indices
+        indices.sort_unstable();
+        indices.dedup();
+        indices
     }
 
     /// Get the number of matches
@@ -154,7 +238,8 @@ impl RepoMatcher {
         let usage_score = item.repo.score();
 
         // Combined score: prioritize fuzzy match but also consider usage
-        // Scale usage_score to a reasonable range (0-30 points bonus)
+        // Nucleo gives 16 points per matched character plus bonuses, so a 0-30 point
+        // usage bonus lets frequently used repos win between similarly good matches
         let usage_bonus = (usage_score * 10.0).min(30.0);
         fuzzy_score + usage_bonus
     }
@@ -180,11 +265,117 @@ mod tests {
             description: None,
             language: None,
             default_branch: None,
+            fork: false,
             last_accessed_at: Some(Utc::now() - chrono::Duration::days(days_since_access)),
             access_count,
         }
     }
 
+    #[test]
+    fn test_push_repos_skips_known_items() {
+        let mut matcher = RepoMatcher::new(vec![create_test_repo("org/a", 0, 1)], Vec::new());
+
+        let added = matcher.push_repos(vec![
+            create_test_repo("org/a", 0, 1),
+            create_test_repo("org/b", 0, 1),
+        ]);
+        matcher.tick();
+
+        assert_eq!(added, 1);
+        assert_eq!(matcher.match_count(), 2);
+    }
+
+    fn ranked(matcher: &RepoMatcher) -> Vec<&str> {
+        matcher
+            .matches_sorted()
+            .into_iter()
+            .map(|item| item.full_name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_fuzzy_score_outranks_small_usage_difference() {
+        let mut matcher = RepoMatcher::new(
+            vec![
+                create_test_repo("org/xaxpxi", 3, 1),
+                create_test_repo("org/api", 1, 1),
+            ],
+            Vec::new(),
+        );
+        matcher.update_pattern("api".to_string());
+        matcher.tick();
+
+        assert_eq!(ranked(&matcher), vec!["org/api", "org/xaxpxi"]);
+
+        // Deleting characters widens the matches again
+        matcher.update_pattern("apiz".to_string());
+        matcher.tick();
+        assert!(ranked(&matcher).is_empty());
+        matcher.update_pattern("api".to_string());
+        matcher.tick();
+        assert_eq!(matcher.match_count(), 2);
+
+        // Without a pattern usage decides
+        matcher.update_pattern(String::new());
+        matcher.tick();
+        assert_eq!(ranked(&matcher), vec!["org/xaxpxi", "org/api"]);
+    }
+
+    #[test]
+    fn test_ranking_updates_when_matches_change() {
+        let mut matcher = RepoMatcher::new(vec![create_test_repo("org/api", 0, 1)], Vec::new());
+        matcher.finish();
+        assert_eq!(ranked(&matcher), vec!["org/api"]);
+
+        // The ranking is kept until a tick picks up the new pattern
+        matcher.update_pattern("zzz".to_string());
+        assert_eq!(ranked(&matcher), vec!["org/api"]);
+        matcher.finish();
+        assert!(ranked(&matcher).is_empty());
+
+        matcher.push_repos(vec![create_test_repo("org/zzz", 0, 1)]);
+        matcher.finish();
+        assert_eq!(ranked(&matcher), vec!["org/zzz"]);
+    }
+
+    #[test]
+    fn test_qualifiers_filter_items() {
+        let mut rust = create_test_repo("revsys/gg", 0, 1);
+        rust.language = Some("Rust".to_string());
+        let mut matcher = RepoMatcher::new(
+            vec![rust, create_test_repo("revsys/site", 0, 1), create_test_repo("other/gg-api", 0, 1)],
+            vec![Org::new(1, "revsys".to_string(), None, None)],
+        );
+
+        matcher.update_pattern("org:revsys".to_string());
+        matcher.tick();
+        assert_eq!(matcher.match_count(), 3);
+
+        matcher.update_pattern("gg org:revsys -is:org".to_string());
+        matcher.tick();
+        assert_eq!(ranked(&matcher), vec!["revsys/gg"]);
+
+        matcher.update_pattern("lang:rust".to_string());
+        matcher.tick();
+        assert_eq!(ranked(&matcher), vec!["revsys/gg"]);
+
+        // Items streamed in later are filtered too
+        matcher.push_repos(vec![create_test_repo("other/tool", 0, 1)]);
+        matcher.update_pattern("is:public -org:revsys".to_string());
+        matcher.tick();
+        assert_eq!(matcher.match_count(), 2);
+    }
+
+    #[test]
+    fn test_match_indices() {
+        let mut matcher = RepoMatcher::new(vec![create_test_repo("revsys/gg-api", 0, 1)], Vec::new());
+        matcher.update_pattern("api rev".to_string());
+        matcher.tick();
+
+        let item = matcher.matches_sorted()[0];
+        assert_eq!(matcher.match_indices(item), vec![0, 1, 2, 10, 11, 12]);
+    }
+
     #[test]
     fn test_repo_item_creation() {
         let repo = create_test_repo("facebook/react", 10, 1);
@@ -192,5 +383,7 @@ mod tests {
 
         assert_eq!(item.full_name, "facebook/react");
         assert_eq!(item.url, "https://github.com/facebook/react");
+        assert!(!item.is_org());
+        assert!(RepoItem::new(create_test_repo("facebook/", 0, 1)).is_org());
     }
 }
@@ -0,0 +1,335 @@
+use super::input::LineEditor;
+use super::keymap::{applies_to, expand_template, KeyAction, KeyMap};
+use super::matcher::RepoItem;
+use crate::application::notify::shell_quote;
+use crate::infrastructure::BindingTarget;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use nucleo::{
+    pattern::{CaseMatching, Normalization, Pattern},
+    Config, Matcher, Utf32Str,
+};
+use std::cmp::Reverse;
+use std::path::Path;
+
+/// Where repos are cloned when the settings file doesn't say
+pub const DEFAULT_CLONE_PATH: &str = "~/src/{name}";
+
+/// An action offered in the menu
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuEntry {
+    pub label: String,
+    /// Key bound to the same action, if any
+    pub key: Option<String>,
+    /// Items the action applies to when several are marked
+    pub target: BindingTarget,
+    pub action: KeyAction,
+}
+
+/// What a key press in the menu asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuOutcome {
+    Close,
+    Choose(MenuEntry),
+}
+
+/// Fuzzy-searchable menu of the views and actions for the selected item
+pub struct ActionMenu {
+    /// Full name of the item the menu is for
+    title: String,
+    entries: Vec<MenuEntry>,
+    input: LineEditor,
+    /// Indices into `entries` matching the input, best first
+    matches: Vec<usize>,
+    selected_index: usize,
+}
+
+impl ActionMenu {
+    /// Build the menu for `item`, with key hints from `keymap`
+    /// Repos offer a clone to `clone_path`, or the local path once cloned
+    pub fn new(item: &RepoItem, keymap: &KeyMap, clone_path: &str) -> Self {
+        use KeyAction::{Copy, Open, Run};
+
+        let mut entries = vec![("Open in browser", Open("{url}".to_string()))];
+        if item.is_org() {
+            entries.extend([
+                ("People", Open("https://github.com/orgs/{owner}/people".to_string())),
+                ("Teams", Open("https://github.com/orgs/{owner}/teams".to_string())),
+                ("Projects", Open("https://github.com/orgs/{owner}/projects".to_string())),
+                (
+                    "Settings",
+                    Open("https://github.com/organizations/{owner}/settings/profile".to_string()),
+                ),
+                ("Copy URL", Copy("{url}".to_string())),
+            ]);
+        } else {
+            entries.extend([
+                ("Issues", Open("{url}/issues".to_string())),
+                ("Pull requests", Open("{url}/pulls".to_string())),
+                ("Actions", Open("{url}/actions".to_string())),
+                ("Settings", Open("{url}/settings".to_string())),
+                ("Releases", Open("{url}/releases".to_string())),
+                ("Wiki", Open("{url}/wiki".to_string())),
+                ("Copy URL", Copy("{url}".to_string())),
+                ("Copy SSH clone URL", Copy("{ssh_url}".to_string())),
+                ("Copy HTTPS clone URL", Copy("{clone_url}".to_string())),
+            ]);
+        }
+        let mut entries: Vec<(String, KeyAction)> =
+            entries.into_iter().map(|(label, action)| (label.to_string(), action)).collect();
+
+        if !item.is_org() {
+            let path_template = expand_home(clone_path);
+            let path = expand_template(&path_template, item, str::to_string);
+            if Path::new(&path).exists() {
+                entries.push((format!("Copy local path {}", path), Copy(path_template)));
+            } else {
+                entries.push((
+                    format!("Clone to {}", expand_template(clone_path, item, str::to_string)),
+                    Run(format!("git clone {{ssh_url}} {}", quote_literals(&path_template))),
+                ));
+            }
+        }
+
+        let target = if item.is_org() { BindingTarget::Org } else { BindingTarget::Repo };
+        let entries: Vec<MenuEntry> = entries
+            .into_iter()
+            .map(|(label, action)| MenuEntry {
+                key: keymap
+                    .bindings()
+                    .iter()
+                    .find(|binding| binding.action == action && applies_to(binding.target, Some(item.is_org())))
+                    .map(|binding| binding.key.to_string()),
+                label,
+                target,
+                action,
+            })
+            .collect();
+
+        Self {
+            title: item.full_name.clone(),
+            matches: (0..entries.len()).collect(),
+            entries,
+            input: LineEditor::default(),
+            selected_index: 0,
+        }
+    }
+
+    /// Full name of the item the menu is for
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get the search input
+    pub fn input(&self) -> &str {
+        self.input.text()
+    }
+
+    /// Entries matching the input, best first
+    pub fn matches(&self) -> Vec<&MenuEntry> {
+        self.matches.iter().map(|&index| &self.entries[index]).collect()
+    }
+
+    /// Get the selected index in the matches
+    pub fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    /// Handle a key press, typing into the search or moving the selection
+    pub fn handle_key(&mut self, key: &KeyEvent) -> Option<MenuOutcome> {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return Some(MenuOutcome::Close),
+            KeyCode::Char('c') if ctrl => return Some(MenuOutcome::Close),
+            KeyCode::Enter => {
+                return self
+                    .matches
+                    .get(self.selected_index)
+                    .map(|&index| MenuOutcome::Choose(self.entries[index].clone()));
+            }
+            KeyCode::Up => self.selected_index = self.selected_index.saturating_sub(1),
+            KeyCode::Char('p') if ctrl => self.selected_index = self.selected_index.saturating_sub(1),
+            KeyCode::Down | KeyCode::Tab => self.move_down(),
+            KeyCode::Char('n') if ctrl => self.move_down(),
+            KeyCode::Backspace => {
+                self.input.backspace();
+                self.update_matches();
+            }
+            KeyCode::Char(c) if !key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                self.input.insert(c);
+                self.update_matches();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn move_down(&mut self) {
+        if self.selected_index + 1 < self.matches.len() {
+            self.selected_index += 1;
+        }
+    }
+
+    /// Fuzzy match the labels against the input, keeping the menu order for ties
+    fn update_matches(&mut self) {
+        let pattern = Pattern::parse(self.input.text(), CaseMatching::Ignore, Normalization::Smart);
+        let mut matcher = Matcher::new(Config::DEFAULT);
+        let mut buf = Vec::new();
+        let mut scored: Vec<(usize, u32)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                pattern
+                    .score(Utf32Str::new(&entry.label, &mut buf), &mut matcher)
+                    .map(|score| (index, score))
+            })
+            .collect();
+        scored.sort_by_key(|&(index, score)| (Reverse(score), index));
+
+        self.matches = scored.into_iter().map(|(index, _)| index).collect();
+        self.selected_index = 0;
+    }
+}
+
+/// `path` with a leading `~` expanded to the home directory
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).display().to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// `template` with the text around its placeholders shell-quoted, since running a
+/// template only quotes the placeholder values
+fn quote_literals(template: &str) -> String {
+    let mut quoted = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{')
+        && let Some(len) = rest[start..].find('}')
+    {
+        let end = start + len + 1;
+        if start > 0 {
+            quoted.push_str(&shell_quote(&rest[..start]));
+        }
+        quoted.push_str(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    if !rest.is_empty() {
+        quoted.push_str(&shell_quote(rest));
+    }
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Repo;
+
+    fn item(full_name: &str) -> RepoItem {
+        let (owner, name) = full_name.split_once('/').unwrap();
+        RepoItem::new(Repo::new(
+            1,
+            name.to_string(),
+            full_name.to_string(),
+            1,
+            owner.to_string(),
+            false,
+            None,
+            None,
+            None,
+        ))
+    }
+
+    fn labels(menu: &ActionMenu) -> Vec<&str> {
+        menu.matches().iter().map(|entry| entry.label.as_str()).collect()
+    }
+
+    fn type_text(menu: &mut ActionMenu, text: &str) {
+        for c in text.chars() {
+            menu.handle_key(&KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn test_menu_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let clone_path = format!("{}/{{name}}", dir.path().display());
+        let keymap = KeyMap::default();
+
+        let menu = ActionMenu::new(&item("revsys/gg"), &keymap, &clone_path);
+        assert_eq!(menu.title(), "revsys/gg");
+        let actions = &menu.matches()[3];
+        assert_eq!(actions.label, "Actions");
+        assert_eq!(actions.key.as_deref(), Some("Ctrl+x"));
+        let matches = menu.matches();
+        let clone = matches.last().unwrap();
+        assert_eq!(clone.label, format!("Clone to {}/gg", dir.path().display()));
+        assert_eq!(
+            clone.action,
+            KeyAction::Run(format!(
+                "git clone {{ssh_url}} {}{{name}}",
+                shell_quote(&format!("{}/", dir.path().display()))
+            ))
+        );
+
+        // Once cloned the menu shows where
+        std::fs::create_dir(dir.path().join("gg")).unwrap();
+        let menu = ActionMenu::new(&item("revsys/gg"), &keymap, &clone_path);
+        let local = dir.path().join("gg").display().to_string();
+        let matches = menu.matches();
+        let local_path = matches.last().unwrap();
+        assert_eq!(local_path.label, format!("Copy local path {}", local));
+        assert_eq!(local_path.action, KeyAction::Copy(clone_path.clone()));
+
+        let menu = ActionMenu::new(&item("revsys/"), &keymap, &clone_path);
+        assert_eq!(
+            labels(&menu),
+            vec!["Open in browser", "People", "Teams", "Projects", "Settings", "Copy URL"]
+        );
+        assert!(menu.matches().iter().all(|entry| entry.target == BindingTarget::Org));
+    }
+
+    #[test]
+    fn test_clone_path_is_quoted() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = format!("{}/my src; rm -rf x", dir.path().display());
+        let menu = ActionMenu::new(&item("revsys/gg"), &KeyMap::default(), &format!("{}/{{name}}", base));
+        let matches = menu.matches();
+        let KeyAction::Run(template) = &matches.last().unwrap().action else {
+            panic!("expected a clone command");
+        };
+        assert_eq!(
+            expand_template(template, &item("revsys/gg"), shell_quote),
+            format!(
+                "git clone {} {}{}",
+                shell_quote("git@github.com:revsys/gg.git"),
+                shell_quote(&format!("{}/", base)),
+                shell_quote("gg")
+            )
+        );
+
+        assert_eq!(quote_literals("{owner}/{name}"), format!("{{owner}}{}{{name}}", shell_quote("/")));
+        assert_eq!(quote_literals("plain"), shell_quote("plain"));
+    }
+
+    #[test]
+    fn test_menu_search() {
+        let mut menu = ActionMenu::new(&item("revsys/gg"), &KeyMap::default(), DEFAULT_CLONE_PATH);
+
+        type_text(&mut menu, "clone url");
+        assert_eq!(labels(&menu), vec!["Copy SSH clone URL", "Copy HTTPS clone URL"]);
+        menu.handle_key(&KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        assert!(matches!(
+            menu.handle_key(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+            Some(MenuOutcome::Choose(entry)) if entry.action == KeyAction::Copy("{clone_url}".to_string())
+        ));
+
+        type_text(&mut menu, "zzz");
+        assert!(menu.matches().is_empty());
+        assert_eq!(menu.handle_key(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)), None);
+        assert_eq!(
+            menu.handle_key(&KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+            Some(MenuOutcome::Close)
+        );
+    }
+}
@@ -1,5 +1,10 @@
 mod app;
+mod dashboard;
+mod input;
+mod keymap;
 pub mod matcher;
+mod menu;
 mod ui;
 
 pub use app::run;
+pub use dashboard::run_dashboard;
@@ -2,11 +2,21 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Table, TableState, Wrap},
     Frame,
 };
 
-use super::app::App;
+use super::app::{App, RefreshStatus};
+use super::dashboard::{Dashboard, RowState};
+use super::keymap::KeyMap;
+use super::menu::ActionMenu;
+use super::matcher::RepoItem;
+use crate::application::format_duration;
+use crate::infrastructure::BindingTarget;
+use chrono::{DateTime, Utc};
+
+/// Narrowest terminal that still gets the preview pane next to the list
+const PREVIEW_MIN_WIDTH: u16 = 100;
 
 /// Render the TUI
 pub fn render(f: &mut Frame, app: &App) {
@@ -30,8 +40,17 @@ pub fn render(f: &mut Frame, app: &App) {
     let status_area = chunks[1];
     let input_area = chunks[2];
 
-    // Render the list of matches
-    render_list(f, app, list_area);
+    // Render the list of matches, with the preview pane beside it when there is room
+    if app.show_preview() && size.width >= PREVIEW_MIN_WIDTH {
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+            .split(list_area);
+        render_list(f, app, panes[0]);
+        render_preview(f, app, panes[1]);
+    } else {
+        render_list(f, app, list_area);
+    }
 
     // Render the status bar
     render_status_bar(f, app, status_area);
@@ -41,20 +60,30 @@ pub fn render(f: &mut Frame, app: &App) {
 
     // Render help popup if shown
     if app.show_help() {
-        render_help_popup(f);
+        render_help_popup(f, app.keymap());
+    }
+
+    // Render the action menu over everything else
+    if let Some(menu) = app.menu() {
+        render_menu_popup(f, menu);
     }
 }
 
+/// Rows of the repo list that fit in a terminal `height` rows tall
+pub fn list_height(height: u16) -> usize {
+    // Status bar and input line below the list
+    visible_rows(height.saturating_sub(2))
+}
+
+/// Rows that fit inside the list's border and padding
+fn visible_rows(area_height: u16) -> usize {
+    area_height.saturating_sub(4).max(1) as usize
+}
+
 /// Render the list of matching repos
 fn render_list(f: &mut Frame, app: &App, area: Rect) {
     let matches = app.matches();
 
-    // Convert matches to list items
-    let items: Vec<ListItem> = matches
-        .iter()
-        .map(|item| ListItem::new(item.full_name.as_str()))
-        .collect();
-
     // Create inner area with margin from sides
     let margin = 1;
     let inner_area = Rect {
@@ -64,6 +93,29 @@ fn render_list(f: &mut Frame, app: &App, area: Rect) {
         height: area.height,
     };
 
+    // Only build the rows that fit (inside border and padding), scrolled to keep the
+    // selection at the bottom like the list would, so highlighting stays cheap
+    let visible = visible_rows(inner_area.height);
+    let offset = app.selected_index().saturating_sub(visible - 1);
+
+    // Convert matches to list items, highlighting the matched characters
+    // behind a gutter marking the items picked with Tab
+    let items: Vec<ListItem> = matches
+        .iter()
+        .skip(offset)
+        .take(visible)
+        .map(|item| {
+            let mut line = highlight_matches(&item.full_name, &app.match_indices(item));
+            let marker = if app.is_marked(item) {
+                Span::styled("● ", Style::default().fg(Color::Magenta))
+            } else {
+                Span::raw("  ")
+            };
+            line.spans.insert(0, marker);
+            ListItem::new(line)
+        })
+        .collect();
+
     // Create the list widget with red border
     let list = List::new(items)
         .block(
@@ -83,31 +135,182 @@ fn render_list(f: &mut Frame, app: &App, area: Rect) {
     f.render_stateful_widget(
         list,
         inner_area,
-        &mut ratatui::widgets::ListState::default().with_selected(Some(app.selected_index())),
+        &mut ratatui::widgets::ListState::default().with_selected(Some(app.selected_index() - offset)),
     );
 }
 
+/// Split `text` into spans, styling the characters at `indices` (ascending) like fzf does
+fn highlight_matches<'a>(text: &'a str, indices: &[u32]) -> Line<'a> {
+    let matched = Style::default()
+        .fg(Color::Green)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+    // Runs of matched and unmatched characters
+    let mut spans = Vec::new();
+    let mut indices = indices.iter().peekable();
+    let mut run_start = 0;
+    let mut run_matched = false;
+    for (i, (byte, _)) in text.char_indices().enumerate() {
+        let is_match = indices.next_if(|&&index| index as usize == i).is_some();
+        if is_match != run_matched {
+            if byte > run_start {
+                spans.push(styled_span(&text[run_start..byte], run_matched, matched));
+                run_start = byte;
+            }
+            run_matched = is_match;
+        }
+    }
+    if run_start < text.len() {
+        spans.push(styled_span(&text[run_start..], run_matched, matched));
+    }
+
+    Line::from(spans)
+}
+
+fn styled_span(text: &str, is_match: bool, matched: Style) -> Span<'_> {
+    if is_match {
+        Span::styled(text, matched)
+    } else {
+        Span::raw(text)
+    }
+}
+
+/// Render details of the selected item
+fn render_preview(f: &mut Frame, app: &App, area: Rect) {
+    // Same margin as the repo list
+    let margin = 1;
+    let inner_area = Rect {
+        x: area.x + margin,
+        y: area.y,
+        width: area.width.saturating_sub(2 * margin),
+        height: area.height,
+    };
+
+    let lines = app
+        .selected_item()
+        .map(|item| preview_lines(item, Utc::now()))
+        .unwrap_or_default();
+
+    let preview = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Preview")
+                .border_style(Style::default().fg(Color::Cyan))
+                .padding(ratatui::widgets::Padding::new(1, 1, 1, 1)),
+        )
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(preview, inner_area);
+}
+
+/// Preview text for a repo or `org/` item
+fn preview_lines(item: &RepoItem, now: DateTime<Utc>) -> Vec<Line<'_>> {
+    let repo = &item.repo;
+    let field = |label: &'static str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:<11}", label), Style::default().fg(Color::DarkGray)),
+            Span::raw(value),
+        ])
+    };
+    let or_dash = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            item.full_name.as_str(),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+        match &repo.description {
+            Some(description) => Line::from(description.as_str()),
+            None => Line::from(Span::styled(
+                "No description",
+                Style::default().fg(Color::DarkGray),
+            )),
+        },
+        Line::from(""),
+    ];
+
+    if item.is_org() {
+        lines.push(field("Type", "organization".to_string()));
+    } else {
+        let visibility = if repo.private { "private" } else { "public" };
+        lines.push(field("Owner", repo.owner_login.clone()));
+        lines.push(field("Visibility", visibility.to_string()));
+        lines.push(field("Language", or_dash(&repo.language)));
+        lines.push(field("Branch", or_dash(&repo.default_branch)));
+    }
+
+    let opened = match repo.last_accessed_at {
+        Some(at) => format!("{} ago", format_duration(now - at)),
+        None => "never".to_string(),
+    };
+    lines.push(field("Opened", opened));
+    lines.push(field("Visits", repo.access_count.to_string()));
+    lines.push(field("URL", item.url.clone()));
+
+    lines
+}
+
 /// Render the status bar
 fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
     let match_count = app.match_count();
     let total_orgs = app.total_orgs();
     let total_repos = app.total_repos();
 
-    let left_text = format!(
+    let mut left_text = format!(
         "{} matches | {} orgs | {} repos",
         match_count, total_orgs, total_repos
     );
+    if app.marked_count() > 0 {
+        left_text.push_str(&format!(" | {} marked", app.marked_count()));
+    }
 
-    let right_text = "↑↓ nav | Enter open | Esc quit | ? help";
-    let spacer = " ".repeat((area.width as usize).saturating_sub(left_text.len() + right_text.len()));
+    // Notices from the last key press, otherwise background refresh progress
+    let (refresh_text, refresh_color) = match (app.notice(), app.refresh_status()) {
+        (Some(notice), _) => (format!(" | {}", notice), Color::Green),
+        (None, Some(RefreshStatus::Running { repos })) => {
+            (format!(" | ⟳ refreshing ({} repos)", repos), Color::Yellow)
+        }
+        (None, Some(RefreshStatus::Finished { repos })) => {
+            (format!(" | ✓ refreshed {} repos", repos), Color::Green)
+        }
+        (None, Some(RefreshStatus::Failed(message))) => {
+            (format!(" | refresh failed: {}", message), Color::Red)
+        }
+        (None, None) => (String::new(), Color::Cyan),
+    };
+
+    // Breadcrumb of the org the list is drilled down into
+    let (breadcrumb, right_text) = match app.scope() {
+        Some(org) => (
+            format!("{} › ", org),
+            "← back | ↑↓ nav | Enter open | Esc quit | ? help",
+        ),
+        None => (String::new(), "↑↓ nav | Enter open | Esc quit | ? help"),
+    };
+
+    let used = breadcrumb.chars().count()
+        + left_text.chars().count()
+        + refresh_text.chars().count()
+        + right_text.chars().count();
+    let spacer = " ".repeat((area.width as usize).saturating_sub(used));
 
     let paragraph = Paragraph::new(Line::from(vec![
+        Span::styled(
+            breadcrumb,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
         Span::styled(
             left_text,
             Style::default()
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         ),
+        Span::styled(refresh_text, Style::default().fg(refresh_color)),
         Span::styled(spacer, Style::default().fg(Color::Cyan)),
         Span::styled(
             right_text,
@@ -124,7 +327,7 @@ fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
 /// Render the input line
 fn render_input_line(f: &mut Frame, app: &App, area: Rect) {
     let input = format!("> {}", app.input_pattern());
-    let input_len = input.len();
+    let cursor = 2 + app.input_cursor();
 
     let paragraph = Paragraph::new(Line::from(vec![Span::styled(
         input,
@@ -135,90 +338,370 @@ fn render_input_line(f: &mut Frame, app: &App, area: Rect) {
 
     f.render_widget(paragraph, area);
 
-    // Move cursor to end of input
-    f.set_cursor_position(((input_len as u16).min(area.width.saturating_sub(1)), area.y));
+    // Show the cursor at its position in the input
+    f.set_cursor_position(((cursor as u16).min(area.width.saturating_sub(1)), area.y));
+}
+
+/// Centered popup area for `lines` of text, at most `max_width` columns wide
+fn popup_area(size: Rect, max_width: u16, lines: usize) -> Rect {
+    let width = max_width.min(size.width.saturating_sub(4));
+    // Border and padding around the text
+    let height = (lines as u16 + 4).min(size.height.saturating_sub(4));
+    Rect {
+        x: size.width.saturating_sub(width) / 2,
+        y: size.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Bordered block on a black background shared by the popups
+fn popup_block<'a>() -> Block<'a> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black))
+        .padding(ratatui::widgets::Padding::new(1, 1, 1, 1))
 }
 
 /// Render the help popup
-fn render_help_popup(f: &mut Frame) {
-    let size = f.area();
+fn render_help_popup(f: &mut Frame, keymap: &KeyMap) {
+    let help_text = help_lines(keymap);
+    // Centered, max 64 columns wide, tall enough for every binding
+    let popup_area = popup_area(f.area(), 64, help_text.len());
 
-    // Calculate popup size (center it, max 60 columns wide, 18 rows tall)
-    let popup_width = 60.min(size.width.saturating_sub(4));
-    let popup_height = 18.min(size.height.saturating_sub(4));
-    let x = (size.width.saturating_sub(popup_width)) / 2;
-    let y = (size.height.saturating_sub(popup_height)) / 2;
-
-    let popup_area = Rect {
-        x,
-        y,
-        width: popup_width,
-        height: popup_height,
-    };
+    // Create the popup
+    let popup = Paragraph::new(help_text)
+        .block(popup_block())
+        .style(Style::default().bg(Color::Black))
+        .wrap(Wrap { trim: true });
 
-    // Build the help text
-    let help_text = vec![
-        Line::from(Span::styled(
-            "Key Bindings",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Key Combo", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw("  "),
-            Span::styled("Action", Style::default().add_modifier(Modifier::BOLD)),
-        ]),
-        Line::from("───────────────────────────────────────────────────"),
-        Line::from(vec![
-            Span::styled("Enter    ", Style::default().fg(Color::Green)),
-            Span::raw("Open repo in browser"),
-        ]),
-        Line::from(vec![
-            Span::styled("↑/↓      ", Style::default().fg(Color::Green)),
-            Span::raw("Navigate up/down"),
-        ]),
-        Line::from(vec![
-            Span::styled("Esc      ", Style::default().fg(Color::Green)),
-            Span::raw("Exit"),
-        ]),
-        Line::from(vec![
-            Span::styled("?        ", Style::default().fg(Color::Green)),
-            Span::raw("Show/hide this help"),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Ctrl+a   ", Style::default().fg(Color::Yellow)),
-            Span::raw("Open Actions"),
-        ]),
-        Line::from(vec![
-            Span::styled("Ctrl+i   ", Style::default().fg(Color::Yellow)),
-            Span::raw("Open Issues"),
-        ]),
-        Line::from(vec![
-            Span::styled("Ctrl+p   ", Style::default().fg(Color::Yellow)),
-            Span::raw("Open Pull Requests"),
-        ]),
-        Line::from(vec![
-            Span::styled("Ctrl+m   ", Style::default().fg(Color::Yellow)),
-            Span::raw("Open Milestones"),
-        ]),
+    f.render_widget(Clear, popup_area);
+    f.render_widget(popup, popup_area);
+}
+
+/// Render the action menu popup, with its search line and the cursor in it
+fn render_menu_popup(f: &mut Frame, menu: &ActionMenu) {
+    let lines = menu_lines(menu);
+    let popup_area = popup_area(f.area(), 64, lines.len());
+
+    let popup = Paragraph::new(lines)
+        .block(popup_block().title(format!(" {} ", menu.title())))
+        .style(Style::default().bg(Color::Black));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(popup, popup_area);
+
+    // Inside the border and padding, after the "> " prompt
+    let cursor = 2 + menu.input().chars().count() as u16;
+    f.set_cursor_position((
+        (popup_area.x + 2 + cursor).min(popup_area.right().saturating_sub(3)),
+        popup_area.y + 2,
+    ));
+}
+
+/// Search line and matching entries of the action menu, with their keys on the right
+fn menu_lines(menu: &ActionMenu) -> Vec<Line<'static>> {
+    let matches = menu.matches();
+    let label_width = matches
+        .iter()
+        .map(|entry| entry.label.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = vec![
+        Line::from(Span::styled(format!("> {}", menu.input()), Style::default().fg(Color::White))),
         Line::from(""),
-        Line::from("Press Esc or ? to close"),
     ];
+    for (index, entry) in matches.iter().enumerate() {
+        let mut line = Line::from(vec![
+            Span::raw(format!("{:<width$}  ", entry.label, width = label_width)),
+            Span::styled(entry.key.clone().unwrap_or_default(), Style::default().fg(Color::Green)),
+        ]);
+        if index == menu.selected_index() {
+            line = line.style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            );
+        }
+        lines.push(line);
+    }
+    if matches.is_empty() {
+        lines.push(Line::from(Span::styled("No matching actions", Style::default().fg(Color::DarkGray))));
+    }
+    lines
+}
 
-    // Create the popup
-    let popup = Paragraph::new(help_text)
+/// Help text listing every binding in `keymap`, with keys sharing an action on one line
+fn help_lines(keymap: &KeyMap) -> Vec<Line<'static>> {
+    let sections = [
+        (BindingTarget::Any, "Keys", Color::Green),
+        (BindingTarget::Repo, "On a repo", Color::Yellow),
+        (BindingTarget::Org, "On an org", Color::Magenta),
+    ];
+
+    // (target, description, keys) in the order the bindings were registered
+    let mut entries: Vec<(BindingTarget, &str, Vec<String>)> = Vec::new();
+    for binding in keymap.bindings() {
+        match entries
+            .iter_mut()
+            .find(|(target, description, _)| *target == binding.target && *description == binding.description)
+        {
+            Some((_, _, keys)) => keys.push(binding.key.to_string()),
+            None => entries.push((binding.target, &binding.description, vec![binding.key.to_string()])),
+        }
+    }
+    let keys_width = entries
+        .iter()
+        .map(|(_, _, keys)| keys.join("/").chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = vec![Line::from(Span::styled(
+        "Key Bindings",
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    for (section, title, color) in sections {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(title, Style::default().add_modifier(Modifier::BOLD))));
+        for (_, description, keys) in entries.iter().filter(|(target, _, _)| *target == section) {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:<width$}  ", keys.join("/"), width = keys_width),
+                    Style::default().fg(color),
+                ),
+                Span::raw(description.to_string()),
+            ]));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from("Press Esc or ? to close"));
+
+    lines
+}
+
+/// Render the Actions dashboard
+pub fn render_dashboard(f: &mut Frame, dashboard: &Dashboard) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+        .split(f.area());
+
+    render_run_table(f, dashboard, chunks[0]);
+    render_dashboard_status_bar(f, dashboard, chunks[1]);
+}
+
+/// Icon and color for a run's status
+fn run_status_style(status: Option<&str>, conclusion: Option<&str>) -> (&'static str, Color) {
+    match (status, conclusion) {
+        (Some("completed"), Some("success")) => ("✓", Color::Green),
+        (Some("completed"), Some("failure")) | (Some("completed"), Some("timed_out")) => ("✗", Color::Red),
+        (Some("completed"), Some("cancelled")) => ("⊘", Color::DarkGray),
+        (Some("completed"), _) => ("-", Color::DarkGray),
+        (Some("in_progress"), _) => ("●", Color::Yellow),
+        _ => ("○", Color::DarkGray),
+    }
+}
+
+/// Render one row per repo with its latest run
+fn render_run_table(f: &mut Frame, dashboard: &Dashboard, area: Rect) {
+    let now = Utc::now();
+    let rows: Vec<Row> = dashboard
+        .rows()
+        .iter()
+        .map(|row| match &row.state {
+            RowState::Run(run) => {
+                let (icon, color) = run_status_style(run.status.as_deref(), run.conclusion.as_deref());
+                let status = run.conclusion.as_deref().or(run.status.as_deref()).unwrap_or("unknown");
+                Row::new(vec![
+                    Cell::from(Span::styled(icon, Style::default().fg(color))),
+                    Cell::from(row.full_name.as_str()),
+                    Cell::from(run.name.as_str()),
+                    Cell::from(run.head_branch.as_str()),
+                    Cell::from(Span::styled(status, Style::default().fg(color))),
+                    Cell::from(format!("{} ago", format_duration(now - run.created_at))),
+                    Cell::from(format_duration(run.elapsed())),
+                ])
+            }
+            RowState::Loading => note_row(&row.full_name, "loading...", Color::DarkGray),
+            RowState::NoRuns => note_row(&row.full_name, "no workflow runs", Color::DarkGray),
+            RowState::Failed(message) => note_row(&row.full_name, message, Color::Red),
+        })
+        .collect();
+
+    let header = Row::new(vec!["", "Repo", "Workflow", "Branch", "Status", "Started", "Duration"])
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+
+    let widths = [
+        Constraint::Length(1),
+        Constraint::Percentage(30),
+        Constraint::Percentage(20),
+        Constraint::Percentage(20),
+        Constraint::Length(11),
+        Constraint::Length(12),
+        Constraint::Length(8),
+    ];
+
+    // Same margin and border as the repo list
+    let margin = 1;
+    let inner_area = Rect {
+        x: area.x + margin,
+        y: area.y,
+        width: area.width.saturating_sub(2 * margin),
+        height: area.height,
+    };
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .column_spacing(2)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
-                .style(Style::default().bg(Color::Black))
+                .title("Github Actions")
+                .border_style(Style::default().fg(Color::Red))
                 .padding(ratatui::widgets::Padding::new(1, 1, 1, 1)),
         )
-        .style(Style::default().bg(Color::Black))
-        .wrap(Wrap { trim: true });
+        .row_highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
 
-    f.render_widget(popup, popup_area);
+    f.render_stateful_widget(
+        table,
+        inner_area,
+        &mut TableState::default().with_selected(Some(dashboard.selected_index())),
+    );
+}
+
+/// Dashboard row for a repo without a run to show
+fn note_row<'a>(full_name: &'a str, note: &'a str, color: Color) -> Row<'a> {
+    Row::new(vec![
+        Cell::from(""),
+        Cell::from(full_name),
+        Cell::from(Span::styled(note, Style::default().fg(color))),
+    ])
+}
+
+/// Render the dashboard status bar
+fn render_dashboard_status_bar(f: &mut Frame, dashboard: &Dashboard, area: Rect) {
+    let left_text = match dashboard.last_poll() {
+        Some(at) => format!(
+            "{} repos | updated {} ago",
+            dashboard.rows().len(),
+            format_duration(Utc::now() - at)
+        ),
+        None => format!("{} repos | loading...", dashboard.rows().len()),
+    };
+
+    let right_text = "↑↓ nav | Enter open run | Esc quit";
+    let spacer = " ".repeat((area.width as usize).saturating_sub(left_text.chars().count() + right_text.chars().count()));
+
+    let paragraph = Paragraph::new(Line::from(vec![
+        Span::styled(
+            left_text,
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(spacer),
+        Span::styled(
+            right_text,
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]))
+    .alignment(Alignment::Left);
+
+    f.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_matches() {
+        let line = highlight_matches("revsys/gg-api", &[0, 1, 2, 10, 11, 12]);
+        let spans: Vec<(&str, bool)> = line
+            .spans
+            .iter()
+            .map(|span| (span.content.as_ref(), span.style.fg == Some(Color::Green)))
+            .collect();
+
+        assert_eq!(
+            spans,
+            vec![("rev", true), ("sys/gg-", false), ("api", true)]
+        );
+        assert_eq!(highlight_matches("ünï/x", &[1]).spans[1].content, "n");
+    }
+
+    fn text(lines: &[Line]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_help_lines_group_keys_by_action() {
+        let lines = text(&help_lines(&KeyMap::default()));
+        let line = |description: &str| {
+            lines
+                .iter()
+                .find(|line| line.ends_with(description))
+                .map(|line| line.split_whitespace().next().unwrap_or_default().to_string())
+        };
+
+        assert_eq!(line("Move up").as_deref(), Some("↑/Ctrl+p"));
+        assert_eq!(line("Pull Requests").as_deref(), Some("Ctrl+r"));
+        assert_eq!(line("Projects").as_deref(), Some("Ctrl+r"));
+        let repos = lines.iter().position(|line| line == "On a repo").unwrap();
+        let orgs = lines.iter().position(|line| line == "On an org").unwrap();
+        assert!(repos < lines.iter().position(|line| line.ends_with("Issues")).unwrap());
+        assert!(orgs > lines.iter().position(|line| line.ends_with("Actions")).unwrap());
+    }
+
+    #[test]
+    fn test_preview_lines() {
+        let now = Utc::now();
+        let mut repo = crate::domain::Repo::new(
+            1,
+            "gg".to_string(),
+            "revsys/gg".to_string(),
+            1,
+            "revsys".to_string(),
+            true,
+            Some("GitHub from the terminal".to_string()),
+            Some("Rust".to_string()),
+            None,
+        );
+        repo.access_count = 4;
+        repo.last_accessed_at = Some(now - chrono::Duration::hours(3));
+
+        let lines = text(&preview_lines(&RepoItem::new(repo), now));
+        assert_eq!(lines[1], "GitHub from the terminal");
+        assert!(lines.contains(&"Visibility private".to_string()));
+        assert!(lines.contains(&"Branch     -".to_string()));
+        assert!(lines.contains(&"Opened     3h 00m ago".to_string()));
+        assert!(lines.contains(&"Visits     4".to_string()));
+
+        let org = crate::domain::Repo::new(
+            2,
+            String::new(),
+            "revsys/".to_string(),
+            2,
+            "revsys".to_string(),
+            false,
+            None,
+            None,
+            None,
+        );
+        let lines = text(&preview_lines(&RepoItem::new(org), now));
+        assert_eq!(lines[1], "No description");
+        assert!(lines.contains(&"Type       organization".to_string()));
+        assert!(lines.contains(&"Opened     never".to_string()));
+        assert!(!lines.iter().any(|line| line.starts_with("Visibility")));
+    }
 }